@@ -0,0 +1,250 @@
+use std::any::{TypeId, type_name};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use parking_lot::{RwLock, RwLockReadGuard};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::World;
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::resources::Resource;
+use crate::snapshot::link_aliases;
+
+/// Marker trait for components that are included in `World::serialize_state`
+pub trait BinaryComponent: Component + Serialize + DeserializeOwned {}
+
+/// Marker trait for resources that are included in `World::serialize_state`
+pub trait BinaryResource: Resource + Serialize + DeserializeOwned {}
+
+/// Marks the start of a document written by `World::serialize_state`
+const STATE_MAGIC: &[u8; 4] = b"STRY";
+/// Version of the format written by `World::serialize_state`, bumped whenever the format changes
+const STATE_VERSION: u32 = 1;
+
+/// Tags a type name in the header as a component
+const COMPONENT_KIND: u8 = 0;
+/// Tags a type name in the header as a resource
+const RESOURCE_KIND: u8 = 1;
+
+type LoadedComponents = Vec<(Arc<RwLock<dyn Component>>, Option<Entity>)>;
+
+type ComponentSerializer = fn(&World) -> Result<Vec<u8>, bincode::Error>;
+type ComponentDeserializer = fn(&[u8]) -> Result<LoadedComponents, bincode::Error>;
+type ResourceSerializer = fn(&dyn Resource) -> Result<Vec<u8>, bincode::Error>;
+type ResourceDeserializer = fn(&[u8]) -> Result<Arc<RwLock<dyn Resource>>, bincode::Error>;
+
+/// Serializers for every type registered with `World::register_binary_component`
+/// and `World::register_binary_resource`
+#[derive(Clone, Default)]
+pub(crate) struct BinarySerializers {
+    components: HashMap<TypeId, (&'static str, ComponentSerializer, ComponentDeserializer)>,
+    resources: HashMap<TypeId, (&'static str, ResourceSerializer, ResourceDeserializer)>,
+}
+
+fn invalid_data(message: String) -> Box<dyn Error> {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads bytes written by `write_bytes`, only allocating as much as `reader` actually holds
+fn read_bytes<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let len = read_u64(reader)?;
+    let mut bytes = vec![];
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+/// Skips over bytes written by `write_bytes` without keeping them
+fn skip_bytes<R: Read>(reader: &mut R) -> io::Result<()> {
+    let len = read_u64(reader)?;
+    if io::copy(&mut reader.take(len), &mut io::sink())? != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+fn serialize_components<T: BinaryComponent + 'static>(world: &World) -> Result<Vec<u8>, bincode::Error> {
+    let id = TypeId::of::<T>();
+    let guards = world.components
+        .iter()
+        .filter(|(_, t, _)| t == &id)
+        .map(|(v, _, entity)| (*entity, RwLockReadGuard::map(v.read(), |r| {
+            unsafe { &*(r as *const dyn Component as *const T) }
+        })))
+        .collect::<Vec<_>>();
+    let components = guards.iter().map(|(entity, component)| (*entity, &**component)).collect::<Vec<_>>();
+    bincode::serialize(&components)
+}
+
+fn deserialize_components<T: BinaryComponent + 'static>(bytes: &[u8]) -> Result<LoadedComponents, bincode::Error> {
+    let components: Vec<(Option<Entity>, T)> = bincode::deserialize(bytes)?;
+    Ok(components.into_iter().map(|(entity, component)| (Arc::new(RwLock::new(component)) as Arc<RwLock<dyn Component>>, entity)).collect())
+}
+
+fn serialize_resource<T: BinaryResource + 'static>(resource: &dyn Resource) -> Result<Vec<u8>, bincode::Error> {
+    bincode::serialize(unsafe { &*(resource as *const dyn Resource as *const T) })
+}
+
+fn deserialize_resource<T: BinaryResource + 'static>(bytes: &[u8]) -> Result<Arc<RwLock<dyn Resource>>, bincode::Error> {
+    Ok(Arc::new(RwLock::new(bincode::deserialize::<T>(bytes)?)))
+}
+
+impl World {
+    /// Includes components of type `T` in `serialize_state` and `deserialize_state`
+    pub fn register_binary_component<T: BinaryComponent + 'static>(&mut self) -> &mut Self {
+        self.binary_serializers.components.insert(TypeId::of::<T>(), (type_name::<T>(), serialize_components::<T>, deserialize_components::<T>));
+        self.register_component_type::<T>();
+        self
+    }
+
+    /// Includes the resource of type `T` in `serialize_state` and `deserialize_state`
+    pub fn register_binary_resource<T: BinaryResource + 'static>(&mut self) -> &mut Self {
+        self.binary_serializers.resources.insert(TypeId::of::<T>(), (type_name::<T>(), serialize_resource::<T>, deserialize_resource::<T>));
+        self.register_resource_type::<T>();
+        self
+    }
+
+    /// Writes every registered component and resource to `writer` with `bincode`.
+    /// The document starts with a format version and the names of the types it holds, sorted by name,
+    /// followed by a length prefixed section for each of those types.
+    /// Resource aliases aren't written, only the resource they share.
+    ///
+    /// # Errors
+    /// Will return an error if a component or resource fails to serialize or `writer` fails
+    /// # Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::binary::BinaryResource;
+    ///
+    /// #[derive(Clone, Debug, Serialize, Deserialize)]
+    /// struct Score { points: u32 }
+    /// impl Resource for Score {}
+    /// impl BinaryResource for Score {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_binary_resource::<Score>().add_resource(Score { points: 30 });
+    ///
+    /// let mut save = vec![];
+    /// world.serialize_state(&mut save).unwrap();
+    ///
+    /// let mut loaded = World::new();
+    /// loaded.register_binary_resource::<Score>().deserialize_state(save.as_slice()).unwrap();
+    /// assert_eq!(loaded.get_resource::<Score>().points, 30);
+    /// ```
+    pub fn serialize_state<W: Write>(&self, mut writer: W) -> Result<(), Box<dyn Error>> {
+        let mut sections = vec![];
+        for (name, serialize, _) in self.binary_serializers.components.values() {
+            sections.push((COMPONENT_KIND, *name, serialize(self)?));
+        }
+        for (id, (name, serialize, _)) in self.binary_serializers.resources.iter() {
+            if self.canonical_resource_id(*id) != *id {
+                continue;
+            }
+            if let Some(resource) = self.resources.get(id) {
+                sections.push((RESOURCE_KIND, *name, serialize(&*resource.read())?));
+            }
+        }
+        sections.sort_by_key(|(kind, name, _)| (*kind, *name));
+
+        writer.write_all(STATE_MAGIC)?;
+        writer.write_all(&STATE_VERSION.to_le_bytes())?;
+        writer.write_all(&(sections.len() as u64).to_le_bytes())?;
+        for (kind, name, _) in sections.iter() {
+            writer.write_all(&[*kind])?;
+            write_bytes(&mut writer, name.as_bytes())?;
+        }
+        for (_, _, bytes) in sections.iter() {
+            write_bytes(&mut writer, bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Loads components and resources from a document written by `serialize_state`.
+    /// Components of registered types are replaced and resources of registered types are overwritten,
+    /// types in the document that aren't registered are skipped. Replaced and loaded components go through
+    /// `Component::on_remove`, `Component::on_add` and the hooks from `with_component_hook`.
+    ///
+    /// # Errors
+    /// Will return an error if the document is malformed, was written by another format version or `reader` fails,
+    /// the world is left unchanged in that case
+    pub fn deserialize_state<R: Read>(&mut self, mut reader: R) -> Result<&mut Self, Box<dyn Error>> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != STATE_MAGIC {
+            return Err(invalid_data("Not a starry world document".to_string()));
+        }
+
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != STATE_VERSION {
+            return Err(invalid_data(format!("Unsupported world document version: {}", version)));
+        }
+
+        let mut types = vec![];
+        for _ in 0..read_u64(&mut reader)? {
+            let mut kind = [0; 1];
+            reader.read_exact(&mut kind)?;
+            if kind[0] != COMPONENT_KIND && kind[0] != RESOURCE_KIND {
+                return Err(invalid_data(format!("Unknown section kind in world document: {}", kind[0])));
+            }
+            types.push((kind[0], String::from_utf8(read_bytes(&mut reader)?)?));
+        }
+
+        let component_ids = self.binary_serializers.components.iter().map(|(id, (name, _, _))| (*name, *id)).collect::<HashMap<_, _>>();
+        let resource_ids = self.binary_serializers.resources.iter().map(|(id, (name, _, _))| (*name, *id)).collect::<HashMap<_, _>>();
+
+        let mut loaded_components = vec![];
+        let mut loaded_resources = vec![];
+        for (kind, name) in types {
+            let id = match kind {
+                COMPONENT_KIND => component_ids.get(name.as_str()),
+                _ => resource_ids.get(name.as_str())
+            };
+            let Some(id) = id.copied() else {
+                skip_bytes(&mut reader)?;
+                continue;
+            };
+
+            let bytes = read_bytes(&mut reader)?;
+            if kind == COMPONENT_KIND {
+                let (_, _, deserialize) = self.binary_serializers.components[&id];
+                loaded_components.extend(deserialize(&bytes)?.into_iter().map(|(component, entity)| (component, id, entity)));
+            } else {
+                let (_, _, deserialize) = self.binary_serializers.resources[&id];
+                loaded_resources.push((self.canonical_resource_id(id), deserialize(&bytes)?));
+            }
+        }
+
+        let registered = self.binary_serializers.components.keys().copied().collect::<Vec<_>>();
+        self.take_components(|t, _| registered.contains(&t));
+        for entity in loaded_components.iter().filter_map(|(_, _, e)| *e) {
+            self.entity_allocator.reserve(entity);
+        }
+        self.notify_added(&loaded_components);
+        self.components.extend(loaded_components);
+        self.rebuild_entity_index();
+        self.resources.extend(loaded_resources);
+        link_aliases(&mut self.resources, &self.resource_aliases);
+        Ok(self)
+    }
+}
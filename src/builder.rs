@@ -0,0 +1,125 @@
+use crate::component::Component;
+use crate::plugin::{Plugin, PluginGroup};
+use crate::resources::Resource;
+use crate::systems::SystemOrdering;
+use crate::{ExclusiveSystemType, SystemType, ThreadPool, World};
+
+/// Builds a world by value, for setting one up in a single expression
+///
+/// ```
+/// use starry_ecs::builder::WorldBuilder;
+/// use starry_ecs::component::Component;
+/// use starry_ecs::resources::Resource;
+/// use starry_ecs::systems::DefaultOrdering;
+/// use starry_ecs::World;
+///
+/// #[derive(Clone, Debug)]
+/// struct Position { x: i32 }
+/// impl Component for Position {}
+///
+/// #[derive(Clone, Debug)]
+/// struct Gravity(f32);
+/// impl Resource for Gravity {}
+///
+/// fn fall(_: &World) {}
+///
+/// let mut world = WorldBuilder::new()
+///     .add_component(Position { x: 0 })
+///     .add_resource(Gravity(9.8))
+///     .add_system(DefaultOrdering::Run, fall)
+///     .build();
+///
+/// world.single_step();
+/// ```
+pub struct WorldBuilder {
+    world: World
+}
+
+impl Default for WorldBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorldBuilder {
+    /// Creates a builder for an empty world
+    pub fn new() -> Self {
+        Self { world: World::new() }
+    }
+
+    /// Creates a builder for an empty world that runs systems in `pool`, see `World::with_thread_pool`
+    pub fn with_thread_pool(pool: ThreadPool) -> Self {
+        Self { world: World::with_thread_pool(pool) }
+    }
+
+    /// Same as `World::add_component`
+    pub fn add_component<T: Component + 'static>(mut self, component: T) -> Self {
+        self.world.add_component(component);
+        self
+    }
+
+    /// Same as `World::add_resource`
+    pub fn add_resource<T: Resource + 'static>(mut self, resource: T) -> Self {
+        self.world.add_resource(resource);
+        self
+    }
+
+    /// Same as `World::add_system`
+    pub fn add_system<S: SystemOrdering + Copy>(mut self, system_ordering: S, system: SystemType) -> Self {
+        self.world.add_system(system_ordering, system);
+        self
+    }
+
+    /// Same as `World::add_system_after`
+    pub fn add_system_after<S: SystemOrdering + Copy>(mut self, system_ordering: S, existing: SystemType, system: SystemType) -> Self {
+        self.world.add_system_after(system_ordering, existing, system);
+        self
+    }
+
+    /// Same as `World::add_system_before`
+    pub fn add_system_before<S: SystemOrdering + Copy>(mut self, system_ordering: S, existing: SystemType, system: SystemType) -> Self {
+        self.world.add_system_before(system_ordering, existing, system);
+        self
+    }
+
+    /// Same as `World::add_exclusive_system`
+    pub fn add_exclusive_system<S: SystemOrdering + Copy>(mut self, system_ordering: S, system: ExclusiveSystemType) -> Self {
+        self.world.add_exclusive_system(system_ordering, system);
+        self
+    }
+
+    /// Same as `World::add_stage`
+    pub fn add_stage(mut self, name: &'static str, priority: i32) -> Self {
+        self.world.add_stage(name, priority);
+        self
+    }
+
+    /// Same as `World::add_system_to_stage`
+    pub fn add_system_to_stage(mut self, name: &'static str, system: SystemType) -> Self {
+        self.world.add_system_to_stage(name, system);
+        self
+    }
+
+    /// Same as `World::add_startup_system`
+    pub fn add_startup_system(mut self, system: SystemType) -> Self {
+        self.world.add_startup_system(system);
+        self
+    }
+
+    /// Same as `World::add_plugin`
+    pub fn add_plugin<P: Plugin>(mut self, plugin: P) -> Self {
+        self.world.add_plugin(plugin);
+        self
+    }
+
+    /// Same as `World::add_plugin_group`
+    pub fn add_plugin_group<G: PluginGroup>(mut self, group: G) -> Self {
+        self.world.add_plugin_group(group);
+        self
+    }
+
+    /// Finishes building and returns the world
+    pub fn build(self) -> World {
+        self.world
+    }
+}
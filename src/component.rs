@@ -1,8 +1,85 @@
+use std::any::TypeId;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use dyn_clone::{DynClone, clone_trait_object};
+use parking_lot::lock_api::ArcRwLockReadGuard;
+use parking_lot::{Mutex, MutexGuard, RawRwLock, RwLock};
+
+use crate::World;
+use crate::entity::Entity;
 
 /// Marker trait for saying what's a Component
-pub trait Component: DynClone + Debug {}
+pub trait Component: DynClone + Debug {
+    /// Called right before the component is added to a world, does nothing by default
+    fn on_add(&mut self, _world: &World) {}
+
+    /// Called right after the component is removed from a world, does nothing by default
+    fn on_remove(&mut self, _world: &World) {}
+}
 
 clone_trait_object!(Component);
+
+/// Derives `Component` along with `Clone` and `Debug`
+#[cfg(feature = "derive")]
+pub use starry_ecs_derive::Component;
+
+/// Every component in a world with its type and the entity it belongs to
+pub(crate) type ComponentStorage = Vec<(Arc<RwLock<dyn Component>>, TypeId, Option<Entity>)>;
+
+/// Hook registered with `World::with_component_hook`, called with a type erased component
+pub(crate) type ComponentHook = Arc<dyn Fn(&dyn Component, &World) + Send + Sync>;
+
+/// Read guards kept alive for pointers handed out by `World::component_ptr`.
+/// The locks belong to the world that took them, so a cloned world starts without any.
+#[derive(Default)]
+pub(crate) struct HeldComponents(Mutex<HeldGuards>);
+
+/// Read guards for every held component, keyed by the address of the component
+type HeldGuards = HashMap<usize, Vec<ArcRwLockReadGuard<RawRwLock, dyn Component>>>;
+
+impl HeldComponents {
+    /// Locks the guards for adding or releasing one
+    pub(crate) fn lock(&self) -> MutexGuard<'_, HeldGuards> {
+        self.0.lock()
+    }
+
+    /// Whether `component` is read locked by a pointer from `World::component_ptr`
+    pub(crate) fn holds(&self, component: &RwLock<dyn Component>) -> bool {
+        self.0.lock().contains_key(&(component.data_ptr() as *const () as usize))
+    }
+}
+
+impl Clone for HeldComponents {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Function that clones a type erased component into a new lock
+pub(crate) type ComponentCloner = fn(&dyn Component) -> Arc<RwLock<dyn Component>>;
+
+/// Clones a component of type `T` into a new lock
+pub(crate) fn clone_locked<T: Component + 'static>(component: &dyn Component) -> Arc<RwLock<dyn Component>> {
+    let component = unsafe { &*(component as *const dyn Component as *const T) };
+    Arc::new(RwLock::new(dyn_clone::clone(component)))
+}
+
+/// Function that compares two type erased components of the same type
+pub(crate) type ComponentEq = fn(&dyn Component, &dyn Component) -> bool;
+
+/// Compares two components of type `T` with `PartialEq`
+pub(crate) fn eq_erased<T: Component + PartialEq + 'static>(a: &dyn Component, b: &dyn Component) -> bool {
+    let a = unsafe { &*(a as *const dyn Component as *const T) };
+    let b = unsafe { &*(b as *const dyn Component as *const T) };
+    a == b
+}
+
+/// Turns a type erased component back into its concrete type
+///
+/// # Safety
+/// The component must be of type `T`
+pub(crate) unsafe fn downcast_locked<T: Component + 'static>(component: Arc<RwLock<dyn Component>>) -> Arc<RwLock<T>> {
+    Arc::from_raw(Arc::into_raw(component) as *const RwLock<T>)
+}
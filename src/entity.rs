@@ -0,0 +1,290 @@
+use std::any::{TypeId, type_name};
+use std::collections::HashSet;
+
+use parking_lot::{RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{ComponentReadGuard, ComponentWriteGuard, StarryError, World};
+use crate::component::Component;
+
+/// A handle to a group of components in a `World`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entity {
+    index: u32,
+    generation: u32
+}
+
+impl Entity {
+    /// The slot of the entity
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// How many times the slot of the entity has been reused
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Hands out entities, reusing the slots of despawned entities with a new generation
+#[derive(Clone, Debug, Default)]
+pub struct EntityAllocator {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<(u32, u32)>
+}
+
+impl EntityAllocator {
+    /// Creates an empty allocator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocates an entity, reusing a freed slot if there is one
+    pub fn allocate(&mut self) -> Entity {
+        let (index, generation) = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.generations.push(0);
+                self.alive.push(false);
+                (self.generations.len() as u32 - 1, 0)
+            }
+        };
+
+        self.generations[index as usize] = generation;
+        self.alive[index as usize] = true;
+        Entity { index, generation }
+    }
+
+    /// Frees an entity so its slot can be reused, returns `false` if the entity was already dead
+    pub fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        self.alive[entity.index as usize] = false;
+        self.free.push((entity.index, entity.generation.wrapping_add(1)));
+        true
+    }
+
+    /// Whether the entity was allocated and hasn't been freed since
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        self.alive.get(index) == Some(&true) && self.generations[index] == entity.generation
+    }
+
+    /// Marks an entity created elsewhere, such as a loaded save, as alive
+    pub fn reserve(&mut self, entity: Entity) {
+        let index = entity.index as usize;
+        while self.generations.len() <= index {
+            self.free.push((self.generations.len() as u32, 0));
+            self.generations.push(0);
+            self.alive.push(false);
+        }
+
+        self.free.retain(|(i, _)| *i != entity.index);
+        self.generations[index] = entity.generation;
+        self.alive[index] = true;
+    }
+}
+
+/// Adds components to a newly spawned entity, returned by `World::spawn`
+pub struct EntityBuilder<'a> {
+    world: &'a mut World,
+    entity: Entity
+}
+
+impl EntityBuilder<'_> {
+    /// Adds a component to the entity
+    pub fn with<T: Component + 'static>(self, component: T) -> Self {
+        self.world.insert_component(component, Some(self.entity));
+        self
+    }
+
+    /// The entity being built
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl World {
+    /// Spawns a new entity
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Position { x: 0 }).id();
+    ///
+    /// assert_eq!(world.entities(), vec![entity]);
+    /// ```
+    pub fn spawn(&mut self) -> EntityBuilder<'_> {
+        let entity = self.entity_allocator.allocate();
+        EntityBuilder { world: self, entity }
+    }
+
+    /// Removes every component belonging to an entity
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Position { x: 0 }).id();
+    /// world.despawn(entity);
+    ///
+    /// assert!(world.try_get_components::<Position>().is_err());
+    /// ```
+    pub fn despawn(&mut self, entity: Entity) -> &mut Self {
+        if self.entity_allocator.free(entity) {
+            self.take_components(|_, e| e == Some(entity));
+        }
+        self
+    }
+
+    /// Removes every component and entity, calling `Component::on_remove` on each component.
+    /// Entities are handed out from scratch afterwards, resources and systems are left alone.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Position { x: 0 }).id();
+    /// world.add_component(Position { x: 1 }).despawn_all();
+    ///
+    /// assert!(!world.is_alive(entity));
+    /// assert!(world.try_get_components::<Position>().is_err());
+    /// assert_eq!(world.spawn().id(), entity);
+    /// ```
+    pub fn despawn_all(&mut self) -> &mut Self {
+        self.take_components(|_, _| true);
+        self.entity_allocator = EntityAllocator::new();
+        self
+    }
+
+    /// Removes every component of type `T`, calling `Component::on_remove` on each. Entities stay alive.
+    pub fn despawn_all_of_type<T: Component + 'static>(&mut self) -> &mut Self {
+        let id = TypeId::of::<T>();
+        self.take_components(|t, _| t == id);
+        self
+    }
+
+    /// Whether the entity was spawned and hasn't been despawned since
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().id();
+    /// world.despawn(entity);
+    ///
+    /// assert!(!world.is_alive(entity));
+    ///
+    /// let reused = world.spawn().id();
+    /// assert!(world.is_alive(reused));
+    /// ```
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entity_allocator.is_alive(entity)
+    }
+
+    /// Adds a component to an entity that was already spawned, like `EntityBuilder::with`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::EntityNotAlive` if the entity isn't alive, nothing is added in that case
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Health { points: i32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().id();
+    /// world.add_component_with_entity(entity, Health { points: 100 });
+    ///
+    /// assert_eq!(world.get_component_by_entity::<Health>(entity).points, 100);
+    /// ```
+    pub fn try_add_component_with_entity<T: Component + 'static>(&mut self, entity: Entity, component: T) -> Result<&mut Self, StarryError> {
+        if !self.entity_allocator.is_alive(entity) {
+            return Err(StarryError::EntityNotAlive(entity));
+        }
+        Ok(self.insert_component(component, Some(entity)))
+    }
+
+    /// Same as `try_add_component_with_entity` but unwraps the value
+    pub fn add_component_with_entity<T: Component + 'static>(&mut self, entity: Entity, component: T) -> &mut Self {
+        self.try_add_component_with_entity(entity, component).unwrap()
+    }
+
+    /// Gets the component of type `T` belonging to an entity and returns a Read guard
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if the entity doesn't have the component
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Position { x: 0 });
+    /// let entity = world.spawn().with(Position { x: 5 }).id();
+    ///
+    /// world.get_component_mut_by_entity::<Position>(entity).x += 1;
+    /// assert_eq!(world.get_component_by_entity::<Position>(entity).x, 6);
+    /// ```
+    pub fn try_get_component_by_entity<T: Component + 'static>(&self, entity: Entity) -> Result<ComponentReadGuard<'_, T>, StarryError> {
+        let index = self.entity_index.get(&(TypeId::of::<T>(), entity)).ok_or(StarryError::ComponentNotFound(type_name::<T>()))?;
+
+        Ok(RwLockReadGuard::map(self.components[*index].0.read(), |r| {
+            unsafe { &*(r as *const dyn Component as *const T) }
+        }))
+    }
+
+    /// Same as `try_get_component_by_entity` but unwraps the value
+    pub fn get_component_by_entity<T: Component + 'static>(&self, entity: Entity) -> ComponentReadGuard<'_, T> {
+        self.try_get_component_by_entity::<T>(entity).unwrap()
+    }
+
+    /// Gets the component of type `T` belonging to an entity and returns a Write guard
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if the entity doesn't have the component
+    pub fn try_get_component_mut_by_entity<T: Component + 'static>(&self, entity: Entity) -> Result<ComponentWriteGuard<'_, T>, StarryError> {
+        let index = self.entity_index.get(&(TypeId::of::<T>(), entity)).ok_or(StarryError::ComponentNotFound(type_name::<T>()))?;
+
+        Ok(RwLockWriteGuard::map(self.components[*index].0.write(), |r| {
+            unsafe { &mut *(r as *mut dyn Component as *mut T) }
+        }))
+    }
+
+    /// Same as `try_get_component_mut_by_entity` but unwraps the value
+    pub fn get_component_mut_by_entity<T: Component + 'static>(&self, entity: Entity) -> ComponentWriteGuard<'_, T> {
+        self.try_get_component_mut_by_entity::<T>(entity).unwrap()
+    }
+
+    /// Lists every entity that has components, in the order their first component was added
+    pub fn entities(&self) -> Vec<Entity> {
+        let mut seen = HashSet::new();
+        self.components.iter().filter_map(|(_, _, e)| *e).filter(|entity| seen.insert(*entity)).collect()
+    }
+}
@@ -0,0 +1,95 @@
+use std::any::{Any, TypeId};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::resources::Resource;
+use crate::World;
+
+/// A function called right away with every event of a certain type, see `World::add_observer`
+pub(crate) type Observer = Arc<dyn Fn(&World, &dyn Any) + Send + Sync>;
+
+/// Events of type `E` sent with `World::send_event`, kept until they're drained or cleared
+#[derive(Clone, Debug)]
+pub struct Events<E: Clone + Debug + Send + Sync + 'static> {
+    events: Vec<E>
+}
+impl<E: Clone + Debug + Send + Sync + 'static> Resource for Events<E> {}
+
+impl<E: Clone + Debug + Send + Sync + 'static> Default for Events<E> {
+    fn default() -> Self {
+        Self { events: vec![] }
+    }
+}
+
+impl<E: Clone + Debug + Send + Sync + 'static> Events<E> {
+    /// Iterates over every event sent so far, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &E> {
+        self.events.iter()
+    }
+
+    /// Removes every event and returns them, oldest first
+    pub fn drain(&mut self) -> Vec<E> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Removes every event
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Counts the events sent so far
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if there are no events
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl World {
+    /// Adds the `Events<E>` resource so events of type `E` are kept for systems to read
+    pub fn add_event<E: Clone + Debug + Send + Sync + 'static>(&mut self) -> &mut Self {
+        self.add_resource(Events::<E>::default())
+    }
+
+    /// Adds an observer that's called as soon as an event of type `E` is sent, before `send_event` returns.
+    /// Observers run on the thread that sent the event, in the order they were added.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct ButtonClicked { id: u32 }
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Clicks(u32);
+    /// impl Resource for Clicks {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Clicks(0)).add_observer(|world: &World, _: &ButtonClicked| {
+    ///     world.get_resource_mut::<Clicks>().0 += 1;
+    /// });
+    ///
+    /// world.send_event(ButtonClicked { id: 1 });
+    /// assert_eq!(world.get_resource::<Clicks>().0, 1);
+    /// ```
+    pub fn add_observer<E: Send + Sync + 'static, F: Fn(&World, &E) + Send + Sync + 'static>(&mut self, f: F) -> &mut Self {
+        let observer: Observer = Arc::new(move |world, event| f(world, event.downcast_ref::<E>().unwrap()));
+        self.observers.entry(TypeId::of::<E>()).or_default().push(observer);
+        self
+    }
+
+    /// Sends an event, calling every observer of `E` right away.
+    /// The event is then stored in the `Events<E>` resource if it was added with `add_event`.
+    pub fn send_event<E: Clone + Debug + Send + Sync + 'static>(&self, event: E) {
+        for observer in self.observers.get(&TypeId::of::<E>()).into_iter().flatten() {
+            observer(self, &event);
+        }
+        if let Ok(mut events) = self.try_get_resource_mut::<Events<E>>() {
+            events.events.push(event);
+        }
+    }
+}
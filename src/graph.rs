@@ -0,0 +1,96 @@
+use crate::{StarryError, World};
+
+/// One step of the execution plan, every system in it can run at the same time
+struct Step {
+    priority: i32,
+    systems: Vec<String>,
+    exclusive: bool
+}
+
+/// The order the systems of a world run in, for turning into a Graphviz diagram with `to_dot`.
+/// Systems are named by their `SystemMeta` if they were described with `World::describe_system`.
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::graph::WorldGraph;
+/// use starry_ecs::systems::{DefaultOrdering, SystemMeta};
+///
+/// fn input(_: &World) {}
+/// fn physics(_: &World) {}
+/// fn render(_: &World) {}
+///
+/// let mut world = World::new();
+/// world
+///     .add_system(DefaultOrdering::Run, input)
+///     .add_system(DefaultOrdering::Run, physics)
+///     .add_system(DefaultOrdering::PostRun, render)
+///     .describe_system(render, SystemMeta::new("render"));
+///
+/// let dot = WorldGraph::from_world(&world).to_dot();
+/// assert!(dot.starts_with("digraph systems {"));
+/// assert!(dot.contains("label=\"render\""));
+/// ```
+pub struct WorldGraph {
+    steps: Vec<Step>
+}
+
+impl WorldGraph {
+    /// Collects the execution plan of the active schedule of `world`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::CyclicDependency` if systems in a priority group depend on each other
+    pub fn try_from_world(world: &World) -> Result<WorldGraph, StarryError> {
+        let plan = world.schedule.execution_plan()?;
+
+        let mut steps = vec![];
+        for (priority, batches, exclusive) in plan.groups.iter() {
+            for batch in batches {
+                let systems = batch.iter().map(|system| world.schedule.system_name(*system)).collect();
+                steps.push(Step { priority: *priority, systems, exclusive: false });
+            }
+            for system in exclusive {
+                let systems = vec![world.schedule.exclusive_system_name(*system)];
+                steps.push(Step { priority: *priority, systems, exclusive: true });
+            }
+        }
+        Ok(WorldGraph { steps })
+    }
+
+    /// Same as `try_from_world` but unwraps the value
+    pub fn from_world(world: &World) -> WorldGraph {
+        Self::try_from_world(world).unwrap()
+    }
+
+    /// Writes the graph in the DOT language. Systems that run in parallel share a rank,
+    /// and every system has an edge to each system of the step that runs after it.
+    /// Exclusive systems are drawn as boxes.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph systems {\n");
+        let mut ids: Vec<Vec<String>> = vec![];
+        let mut next_id = 0;
+
+        for (i, step) in self.steps.iter().enumerate() {
+            dot.push_str(&format!("    subgraph step_{} {{\n        rank=same;\n", i));
+            let mut step_ids = vec![];
+            for system in step.systems.iter() {
+                let id = format!("system_{}", next_id);
+                next_id += 1;
+                let shape = if step.exclusive { "box" } else { "ellipse" };
+                dot.push_str(&format!("        {} [label=\"{}\", shape={}, tooltip=\"priority {}\"];\n", id, system.replace('"', "\\\""), shape, step.priority));
+                step_ids.push(id);
+            }
+            dot.push_str("    }\n");
+            ids.push(step_ids);
+        }
+
+        for pair in ids.windows(2) {
+            for from in pair[0].iter() {
+                for to in pair[1].iter() {
+                    dot.push_str(&format!("    {} -> {};\n", from, to));
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
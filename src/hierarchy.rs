@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::{ComponentReadGuard, World};
+
+/// Makes an entity the child of another entity.
+/// The parent can only be changed with `World::set_parent`, which keeps the index of children up to date.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Parent(Entity);
+impl Component for Parent {}
+
+impl Parent {
+    /// Creates a parent component pointing at `parent`
+    pub fn new(parent: Entity) -> Self {
+        Self(parent)
+    }
+
+    /// The parent entity
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+impl World {
+    /// Rebuilds the index from parents to their children from the `Parent` components in storage
+    pub(crate) fn rebuild_children_index(&mut self) {
+        let children = self.entities()
+            .into_iter()
+            .filter_map(|child| Some((self.try_get_component_by_entity::<Parent>(child).ok()?.get(), child)))
+            .collect::<Vec<_>>();
+
+        self.children_index.clear();
+        for (parent, child) in children {
+            self.children_index.entry(parent).or_default().push(child);
+        }
+    }
+
+    /// Makes `child` a child of `parent`, adding a `Parent` component to it if it doesn't have one
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let root = world.spawn().id();
+    /// let other = world.spawn().id();
+    /// let child = world.spawn().id();
+    ///
+    /// world.set_parent(child, root);
+    /// assert_eq!(world.query_children(root), vec![child]);
+    ///
+    /// world.set_parent(child, other);
+    /// assert!(world.query_children(root).is_empty());
+    /// assert_eq!(world.query_children(other), vec![child]);
+    /// ```
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) -> &mut Self {
+        let previous = self.try_get_component_mut_by_entity::<Parent>(child)
+            .map(|mut component| std::mem::replace(&mut *component, Parent(parent)).get());
+        let Ok(previous) = previous else {
+            self.insert_component(Parent(parent), Some(child));
+            return self;
+        };
+
+        if let Some(siblings) = self.children_index.get_mut(&previous) {
+            siblings.retain(|sibling| *sibling != child);
+        }
+        self.children_index.entry(parent).or_default().push(child);
+        self
+    }
+
+    /// Despawns an entity along with its children, their children and so on.
+    /// Children are despawned before their parents.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let root = world.spawn().id();
+    /// let child = world.spawn().id();
+    /// let grandchild = world.spawn().id();
+    /// world.set_parent(child, root).set_parent(grandchild, child);
+    ///
+    /// world.despawn_recursive(root);
+    /// assert!(!world.is_alive(root) && !world.is_alive(child) && !world.is_alive(grandchild));
+    /// ```
+    pub fn despawn_recursive(&mut self, entity: Entity) -> &mut Self {
+        let mut visited = HashSet::new();
+        let mut stack = vec![entity];
+        let mut entities = vec![];
+        while let Some(entity) = stack.pop() {
+            if visited.insert(entity) {
+                stack.extend(self.query_children(entity));
+                entities.push(entity);
+            }
+        }
+
+        for entity in entities.into_iter().rev() {
+            self.despawn(entity);
+        }
+        self
+    }
+
+    /// Lists the children of an entity, in the order they became its children
+    pub fn query_children(&self, parent: Entity) -> Vec<Entity> {
+        self.children_index.get(&parent).cloned().unwrap_or_default()
+    }
+
+    /// Gets the component of type `T` of every child of an entity, skipping children that don't have one
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::hierarchy::Parent;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Transform { x: i32 }
+    /// impl Component for Transform {}
+    ///
+    /// let mut world = World::new();
+    /// let root = world.spawn().id();
+    /// world.spawn().with(Parent::new(root)).with(Transform { x: 1 });
+    /// world.spawn().with(Parent::new(root));
+    ///
+    /// let children = world.query_with_parent::<Transform>(root);
+    /// assert_eq!(children.len(), 1);
+    /// assert_eq!(children[0].1.x, 1);
+    /// ```
+    pub fn query_with_parent<T: Component + 'static>(&self, parent: Entity) -> Vec<(Entity, ComponentReadGuard<'_, T>)> {
+        self.children_index
+            .get(&parent)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| Some((*child, self.try_get_component_by_entity::<T>(*child).ok()?)))
+            .collect()
+    }
+}
@@ -3,13 +3,17 @@
 //! A very crude parallelized entity-component-system API
 
 #![deny(rust_2018_idioms)]
-#![feature(type_name_of_val)]
-#![feature(thread_spawn_unchecked)]
 #![deny(missing_docs)]
 
-use component::Component;
-use resources::Resource;
-use systems::SystemOrdering;
+use component::{Component, ComponentCloner, ComponentEq, ComponentHook, ComponentStorage, HeldComponents};
+use entity::{Entity, EntityAllocator};
+use event::Observer;
+use hierarchy::Parent;
+use local::LocalStorage;
+use non_send::NonSendStorage;
+use resources::{FrameCount, ReactiveResource, ReactiveWriteGuard, Resource, ResourceAlias, ResourceCloner, ResourceEq, ResourceInitializer, TrackedResource};
+use schedule::{ExecutionPlan, Schedule};
+use systems::{Stage, SystemMeta, SystemOrdering};
 
 /// Trait for Components
 pub mod component;
@@ -17,16 +21,60 @@ pub mod component;
 pub mod resources;
 /// Traits for SystemOrdering and Systems
 pub mod systems;
+/// Sets of systems that can be swapped in and out of a world
+pub mod schedule;
+/// Point-in-time copies of world state
+pub mod snapshot;
+/// Plugins for modular world configuration
+pub mod plugin;
+/// Building worlds by value
+pub mod builder;
+/// Entities that group components together
+pub mod entity;
+/// Typed queries over the components of entities
+pub mod query;
+/// State that belongs to a single system
+pub mod local;
+/// Resources that have to stay on one thread
+pub mod non_send;
+/// State machines that run systems on transitions
+pub mod state;
+/// Events and observers that react to them
+pub mod event;
+/// Parent and child relationships between entities
+pub mod hierarchy;
+/// Systems that take their data as arguments
+pub mod system_param;
+/// Delta time and timers
+pub mod time;
+/// Graphviz diagrams of the order systems run in
+pub mod graph;
+/// JSON serialization of world state
+#[cfg(feature = "serde")]
+pub mod serialization;
+/// Binary serialization of world state
+#[cfg(feature = "bincode")]
+pub mod binary;
 
 
-use std::any::{TypeId, type_name};
+use std::any::{Any, TypeId, type_name};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::collections::hash_map::Entry;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::{Arc};
+use std::time::{Duration, Instant};
 
 use parking_lot::{RwLock, RwLockReadGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLockWriteGuard};
 use thiserror::Error;
 use rayon::prelude::*;
 
+pub use rayon::{ThreadPool, ThreadPoolBuilder};
+
 /// An error type for the `try_get` functions
 #[derive(Error, Debug)]
 pub enum StarryError {
@@ -35,11 +83,39 @@ pub enum StarryError {
     ComponentNotFound(&'static str),
     /// Returns when a certain Resource is not found in World
     #[error("Resource no found of type: `{0}`")]
-    ResourceNotFound(&'static str)
+    ResourceNotFound(&'static str),
+    /// Returns when systems in a priority group depend on each other in a cycle
+    #[error("Cyclic dependency between systems in priority group: `{0}`")]
+    CyclicDependency(i32),
+    /// Returns when a stage with a certain name was never added to World
+    #[error("Stage not found with name: `{0}`")]
+    StageNotFound(&'static str),
+    /// Returns when a lock on a certain Component or Resource couldn't be acquired in time
+    #[error("Timed out acquiring lock of type: `{0}`")]
+    LockTimeout(&'static str),
+    /// Returns when a certain Component or Resource is still shared with another World and can't be moved out
+    #[error("Still shared with another world, type: `{0}`")]
+    StillShared(&'static str),
+    /// Returns when the same component index is asked for more than once
+    #[error("Component index asked for more than once: `{0}`")]
+    DuplicateIndex(usize),
+    /// Returns when two worlds being merged both have a certain Resource
+    #[error("Resource found in both worlds of type: `{0}`")]
+    ResourceConflict(&'static str),
+    /// Returns when a system panics while running with `single_step_recovering` or `run_recovering`
+    #[error("System panicked: `{0}`")]
+    SystemPanic(String),
+    /// Returns when a certain Entity was never spawned or has been despawned
+    #[error("Entity isn't alive: `{0:?}`")]
+    EntityNotAlive(Entity)
 }
 
 /// A reusable alias to make it easier to change system type signature
 pub type SystemType = fn(world: &World);
+/// A reusable alias for systems that need mutable access to the world
+pub type ExclusiveSystemType = fn(world: &mut World);
+/// A handler called with a system that panicked and its panic payload
+pub type ErrorHandler = fn(system: SystemType, payload: Box<dyn Any + Send>);
 // Aliases to make the type signature make more sense
 /// Type alias to a more confusing type
 pub type ResourceWriteGuard<'a, T> = MappedRwLockWriteGuard<'a, T>;
@@ -74,27 +150,121 @@ pub type ComponentReadGuard<'a, T> = MappedRwLockReadGuard<'a, T>;
 /// ```
 #[derive(Clone)]
 pub struct World {
-    components: Vec<(Arc<RwLock<dyn Component>>, TypeId)>,
-    systems: HashMap<i32, Vec<SystemType>>,
-    starting_systems: Vec<SystemType>,
+    components: ComponentStorage,
+    schedule: Schedule,
+    stages: HashMap<&'static str, i32>,
+    entity_allocator: EntityAllocator,
+    entity_index: HashMap<(TypeId, Entity), usize>,
+    locals: LocalStorage,
     resources: HashMap<TypeId, Arc<RwLock<dyn Resource>>>,
+    component_cloners: HashMap<TypeId, ComponentCloner>,
+    component_names: HashMap<TypeId, &'static str>,
+    component_hooks: HashMap<TypeId, (ComponentHook, ComponentHook)>,
+    held_components: HeldComponents,
+    resource_cloners: HashMap<TypeId, ResourceCloner>,
+    resource_names: HashMap<TypeId, &'static str>,
+    resource_aliases: HashMap<TypeId, TypeId>,
+    component_eqs: HashMap<TypeId, ComponentEq>,
+    resource_eqs: HashMap<TypeId, ResourceEq>,
+    change_trackers: HashMap<TypeId, fn(&World)>,
+    resource_initializers: Vec<ResourceInitializer>,
+    non_send_resources: NonSendStorage,
+    thread_pool: Option<Arc<ThreadPool>>,
+    state_transitions: Vec<ExclusiveSystemType>,
+    observers: HashMap<TypeId, Vec<Observer>>,
+    children_index: HashMap<Entity, Vec<Entity>>,
+    param_systems: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    error_callback: Option<fn(StarryError)>,
+    #[cfg(feature = "serde")]
+    serializers: serialization::Serializers,
+    #[cfg(feature = "bincode")]
+    binary_serializers: binary::BinarySerializers,
+    frame: u64,
 }
 
 unsafe impl Send for World {}
 unsafe impl Sync for World {}
 
+impl Default for World {
+    /// Same as `World::new`
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl World {
     /// Creates a new world instance
     pub fn new() -> Self {
         Self {
             components: vec![],
-            systems: HashMap::new(),
-            starting_systems: vec![],
+            schedule: Schedule::new(),
+            stages: HashMap::new(),
+            entity_allocator: EntityAllocator::new(),
+            entity_index: HashMap::new(),
+            locals: LocalStorage::default(),
             resources: HashMap::new(),
+            component_cloners: HashMap::new(),
+            component_names: HashMap::new(),
+            component_hooks: HashMap::new(),
+            held_components: HeldComponents::default(),
+            resource_cloners: HashMap::new(),
+            resource_names: HashMap::new(),
+            resource_aliases: HashMap::new(),
+            component_eqs: HashMap::new(),
+            resource_eqs: HashMap::new(),
+            change_trackers: HashMap::new(),
+            resource_initializers: vec![],
+            non_send_resources: NonSendStorage::default(),
+            thread_pool: None,
+            state_transitions: vec![],
+            observers: HashMap::new(),
+            children_index: HashMap::new(),
+            param_systems: HashMap::new(),
+            error_callback: None,
+            #[cfg(feature = "serde")]
+            serializers: serialization::Serializers::default(),
+            #[cfg(feature = "bincode")]
+            binary_serializers: binary::BinarySerializers::default(),
+            frame: 0,
+        }
+    }
+
+    /// Creates a new world with room for a certain number of components, resources and system priority groups,
+    /// so they can be added without reallocating
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// World::new_with_capacity(10000, 50, 3).single_step();
+    /// ```
+    pub fn new_with_capacity(components: usize, resources: usize, system_groups: usize) -> Self {
+        Self {
+            components: Vec::with_capacity(components),
+            resources: HashMap::with_capacity(resources),
+            schedule: Schedule {
+                systems: HashMap::with_capacity(system_groups),
+                ..Schedule::new()
+            },
+            ..Self::new()
+        }
+    }
+
+    /// Creates a new world that runs systems in `pool` instead of rayon's global thread pool
+    ///
+    /// ```
+    /// use starry_ecs::{ThreadPoolBuilder, World};
+    ///
+    /// let pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    /// World::with_thread_pool(pool).single_step();
+    /// ```
+    pub fn with_thread_pool(pool: ThreadPool) -> Self {
+        Self {
+            thread_pool: Some(Arc::new(pool)),
+            ..Self::new()
         }
     }
 
-    /// Adds a component to the world
+    /// Adds a component to the world, calling `Component::on_add` first
     ///
     /// ```
     /// use starry_ecs::component::Component;
@@ -107,274 +277,2547 @@ impl World {
     /// World::new().add_component(TestComponent { x: 0 });
     /// ```
     pub fn add_component<T: Component + 'static>(&mut self, component: T) -> &mut Self {
-        self.components.push((Arc::new(RwLock::new(component)), TypeId::of::<T>()));
-        self
+        self.insert_component(component, None)
     }
 
-    /// Adds a system with an ordering to the world
+    /// Registers functions that are called whenever a component of type `T` is added to or removed from the world,
+    /// after `Component::on_add` and `Component::on_remove`. Registering hooks again for the same type replaces them.
     ///
     /// ```
-    /// use starry_ecs::systems::DefaultOrdering;
     /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::entity::Entity;
     ///
-    /// fn example_system(_: &World) {
-    ///     println!("Hello, world!");
+    /// #[derive(Clone, Debug)]
+    /// struct Collider { radius: f32 }
+    /// impl Component for Collider {}
+    ///
+    /// fn added(collider: &Collider, _: &World) {
+    ///     println!("Collider with radius {} added", collider.radius);
     /// }
     ///
-    /// World::new().add_system(DefaultOrdering::Run, example_system).single_step();
+    /// fn removed(collider: &Collider, _: &World) {
+    ///     println!("Collider with radius {} removed", collider.radius);
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.with_component_hook(added, removed).spawn().with(Collider { radius: 2.0 }).id();
+    /// world.despawn(entity);
     /// ```
-    pub fn add_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: SystemType) -> &mut Self {
-        self.systems.entry(system_ordering.into()).or_insert(vec![]);
-        self.systems.entry(system_ordering.into()).and_modify(|x| x.push(system));
+    pub fn with_component_hook<T: Component + 'static>(&mut self, on_add: fn(&T, &World), on_remove: fn(&T, &World)) -> &mut Self {
+        let on_add: ComponentHook = Arc::new(move |component, world| on_add(unsafe { &*(component as *const dyn Component as *const T) }, world));
+        let on_remove: ComponentHook = Arc::new(move |component, world| on_remove(unsafe { &*(component as *const dyn Component as *const T) }, world));
+        self.component_hooks.insert(TypeId::of::<T>(), (on_add, on_remove));
         self
     }
 
-    /// Adds a staring system
+    /// Adds `count` copies of a component to the world
     ///
     /// ```
+    /// use starry_ecs::component::Component;
     /// use starry_ecs::World;
     ///
-    /// fn only_ran_once(_: &World) {
-    ///     println!("Hello, World!");
-    /// }
+    /// #[derive(Clone, Debug)]
+    /// pub struct Particle { life: f32 }
+    /// impl Component for Particle {}
     ///
-    /// World::new().add_startup_system(only_ran_once).start();
+    /// let mut world = World::new();
+    /// world.add_component_batch(Particle { life: 1.0 }, 1000);
+    ///
+    /// assert_eq!(world.get_components::<Particle>().len(), 1000);
     /// ```
-    pub fn add_startup_system(&mut self, system: SystemType) -> &mut Self {
-        self.starting_systems.push(system);
-        self
+    pub fn add_component_batch<T: Component + Clone + 'static>(&mut self, component: T, count: usize) -> &mut Self {
+        self.add_component_batch_with(count, |_| component.clone())
     }
 
-    /// Adds a resource to the world.
-    /// There can only be once instance of each resource.
-    /// If an existing resource exists, it will not be replaced.
+    /// Adds `count` components to the world, each made by calling `f` with its position in the batch
     ///
     /// ```
-    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::component::Component;
     /// use starry_ecs::World;
     ///
     /// #[derive(Clone, Debug)]
-    /// pub struct TestResource { x: i32 }
-    /// impl Resource for TestResource {}
-    /// 
-    /// World::new().add_resource(TestResource { x: 0 });
+    /// pub struct Position { x: usize }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component_batch_with(3, |i| Position { x: i * 10 });
+    ///
+    /// assert_eq!(world.get_components::<Position>()[2].x, 20);
     /// ```
-    pub fn add_resource<T: Resource + 'static>(&mut self, resource: T) -> &mut Self {
-        self.resources.entry(TypeId::of::<T>()).or_insert(Arc::new(RwLock::new(resource)));
+    pub fn add_component_batch_with<T: Component + 'static>(&mut self, count: usize, f: impl Fn(usize) -> T) -> &mut Self {
+        self.components.reserve(count);
+        for i in 0..count {
+            self.insert_component(f(i), None);
+        }
         self
     }
-    
-    /// Gets a resource based on a given type `T` and returns a Read guard
+
+    /// Stores a component, optionally belonging to an entity
+    pub(crate) fn insert_component<T: Component + 'static>(&mut self, mut component: T, entity: Option<Entity>) -> &mut Self {
+        component.on_add(self);
+        if let Some((on_add, _)) = self.component_hooks.get(&TypeId::of::<T>()) {
+            on_add(&component, self);
+        }
+        if let Some(entity) = entity {
+            if let Entry::Vacant(slot) = self.entity_index.entry((TypeId::of::<T>(), entity)) {
+                slot.insert(self.components.len());
+                if let Some(parent) = (&component as &dyn Any).downcast_ref::<Parent>() {
+                    self.children_index.entry(parent.get()).or_default().push(entity);
+                }
+            }
+        }
+        self.components.push((Arc::new(RwLock::new(component)), TypeId::of::<T>(), entity));
+        self.register_component_type::<T>();
+        self
+    }
+
+    /// Remembers how to clone and name components of type `T`
+    pub(crate) fn register_component_type<T: Component + 'static>(&mut self) {
+        self.component_cloners.insert(TypeId::of::<T>(), component::clone_locked::<T>);
+        self.component_names.insert(TypeId::of::<T>(), type_name::<T>());
+    }
+
+    /// Remembers how to clone and name resources of type `T`
+    pub(crate) fn register_resource_type<T: Resource + 'static>(&mut self) {
+        self.resource_cloners.insert(TypeId::of::<T>(), resources::clone_locked::<T>);
+        self.resource_names.insert(TypeId::of::<T>(), type_name::<T>());
+    }
+
+    /// Removes every component matching the predicate from storage and returns them in storage order
+    pub(crate) fn take_components(&mut self, predicate: impl Fn(TypeId, Option<Entity>) -> bool) -> ComponentStorage {
+        let taken = self.remove_components(|(_, t, e)| predicate(*t, *e));
+        self.rebuild_entity_index();
+        self.notify_removed(&taken);
+        taken
+    }
+
+    /// Moves the components `remove` returns `true` for out of storage without touching the entity index, keeping their order.
+    /// `remove` is called on every component in storage order. Components locked by `component_ptr` are always kept,
+    /// since calling their `on_remove` would wait on the lock forever.
+    pub(crate) fn remove_components(&mut self, mut remove: impl FnMut(&(Arc<RwLock<dyn Component>>, TypeId, Option<Entity>)) -> bool) -> ComponentStorage {
+        let held = &self.held_components;
+        let (taken, kept) = std::mem::take(&mut self.components)
+            .into_iter()
+            .partition(|component| remove(component) && !held.holds(&component.0));
+        self.components = kept;
+        taken
+    }
+
+    /// Calls `Component::on_add` and the hooks from `with_component_hook` on components that are about to be put in storage
+    fn notify_added(&self, added: &ComponentStorage) {
+        for (component, id, _) in added {
+            let mut component = component.write();
+            component.on_add(self);
+            if let Some((on_add, _)) = self.component_hooks.get(id) {
+                on_add(&*component, self);
+            }
+        }
+    }
+
+    /// Calls `Component::on_remove` and the hooks from `with_component_hook` on components that were just removed from storage
+    fn notify_removed(&self, removed: &ComponentStorage) {
+        for (component, id, _) in removed {
+            let mut component = component.write();
+            component.on_remove(self);
+            if let Some((_, on_remove)) = self.component_hooks.get(id) {
+                on_remove(&*component, self);
+            }
+        }
+    }
+
+    /// Rebuilds the index from entities to component positions after components are moved around
+    pub(crate) fn rebuild_entity_index(&mut self) {
+        self.entity_index.clear();
+        for (i, (_, t, e)) in self.components.iter().enumerate() {
+            if let Some(entity) = e {
+                self.entity_index.entry((*t, *entity)).or_insert(i);
+            }
+        }
+        self.rebuild_children_index();
+    }
+
+    /// Adds a system with an ordering to the world.
+    /// The system runs after every system returned by `SystemOrdering::dependencies`.
     ///
-    /// # Errors
-    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// ```
+    /// use starry_ecs::systems::DefaultOrdering;
+    /// use starry_ecs::World;
+    ///
+    /// fn example_system(_: &World) {
+    ///     println!("Hello, world!");
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, example_system).single_step();
+    /// ```
+    pub fn add_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: SystemType) -> &mut Self {
+        self.schedule.add_system(system_ordering, system);
+        self
+    }
+
+    /// Adds a system that only runs every `n` steps, starting with the first step.
+    /// Steps are counted by the `FrameCount` resource, which is added if it's missing.
+    /// Adding the same system again, with or without another `n`, adds an occurrence that keeps its own interval.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0
     /// # Example
-    /// ```rs
+    /// ```
     /// use starry_ecs::World;
-    /// use starry_ecs::resources::Resource;
     /// use starry_ecs::systems::DefaultOrdering;
     ///
-    /// #[derive(Clone, Debug)]
-    /// struct TestResource { x: i32 }
-    /// impl Resource for TestResource {}
-    ///
-    /// fn test_system(world: &World) {
-    ///     let _resource = world.try_get_resource::<TestResource>().unwrap();
+    /// fn pathfinding(_: &World) {
+    ///     println!("Recalculating paths");
     /// }
     ///
-    /// World::new().add_system(DefaultOrdering::Run, test_system).add_resource(TestResource { x: 0 });
+    /// World::new().add_system_every_n_frames(DefaultOrdering::Run, 10, pathfinding).single_step();
     /// ```
-    pub fn try_get_resource<T: Resource + 'static>(&self) -> Result<ResourceReadGuard<'_, T>, StarryError> {
-        let name = TypeId::of::<T>();
-        let cloned = match self.resources.get(&name) {
-            Some(ok) => ok,
-            None => return Err(StarryError::ResourceNotFound(type_name::<T>()))
-        };
-        Ok(RwLockReadGuard::map(cloned.read(), |r| {
-            unsafe { &*(&*r as *const dyn Resource as *const T) }
-        }))
+    pub fn add_system_every_n_frames<S: SystemOrdering + Copy>(&mut self, system_ordering: S, n: u64, system: SystemType) -> &mut Self {
+        self.add_system_every_n_frames_with_offset(system_ordering, n, 0, system)
     }
 
-    /// Same as `try_get_resource` but unwraps the value
-    pub fn get_resource<T: Resource + 'static>(&self) -> ResourceReadGuard<'_, T> {
-        self.try_get_resource::<T>().unwrap()
+    /// Same as `add_system_every_n_frames` but the system first runs on step `offset`,
+    /// so heavy systems that run every `n` steps can be spread out
+    ///
+    /// # Panics
+    /// Panics if `n` is 0
+    pub fn add_system_every_n_frames_with_offset<S: SystemOrdering + Copy>(&mut self, system_ordering: S, n: u64, offset: u64, system: SystemType) -> &mut Self {
+        assert!(n > 0, "Systems can't run every 0 frames");
+        self.add_resource(FrameCount::default());
+        self.schedule.add_system_every_n_frames(system_ordering, n, offset, system);
+        self
     }
 
-    /// Gets a resource based on a given type `T` and returns a Write guard
+    /// Adds a system that's skipped in steps where the world has no resource of type `T`.
+    /// The condition applies to every occurrence of the system until the last one is removed.
     ///
-    /// # Errors
-    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
-    /// # Example
-    /// ```rs
+    /// ```
     /// use starry_ecs::World;
     /// use starry_ecs::resources::Resource;
     /// use starry_ecs::systems::DefaultOrdering;
     ///
     /// #[derive(Clone, Debug)]
-    /// struct TestResource { x: i32 }
-    /// impl Resource for TestResource {}
+    /// struct Physics { gravity: f32 }
+    /// impl Resource for Physics {}
     ///
-    /// fn test_system(world: &World) {
-    ///     let _resource = world.try_get_resource_mut::<TestResource>().unwrap();
+    /// fn apply_gravity(world: &World) {
+    ///     let _gravity = world.get_resource::<Physics>().gravity;
     /// }
     ///
-    /// World::new().add_system(DefaultOrdering::Run, test_system).add_resource(TestResource { x: 0 });
+    /// let mut world = World::new();
+    /// world.add_system_conditional_on_resource::<Physics, _>(DefaultOrdering::Run, apply_gravity).single_step();
     /// ```
-    pub fn try_get_resource_mut<T: Resource + 'static>(&self) -> Result<ResourceWriteGuard<'_, T>, StarryError> {
-        let name = TypeId::of::<T>();
-        let cloned = match self.resources.get(&name) {
-            Some(ok) => ok,
-            None => return Err(StarryError::ResourceNotFound(type_name::<T>()))
-        };
-        Ok(RwLockWriteGuard::map(cloned.write(), |r| {
-            unsafe { &mut *(&mut *r as *mut dyn Resource as *mut T) }
-        }))
+    pub fn add_system_conditional_on_resource<T: Resource + 'static, S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: SystemType) -> &mut Self {
+        self.schedule.add_run_condition(system, Arc::new(|world: &World| world.resources.contains_key(&TypeId::of::<T>())));
+        self.add_system(system_ordering, system)
     }
 
-    /// Same as `try_get_resource_mut` but unwraps the value
-    pub fn get_resource_mut<T: Resource + 'static>(&self) -> ResourceWriteGuard<'_, T> {
-        self.try_get_resource_mut::<T>().unwrap()
+    /// Adds a system that runs after `existing` has finished, in the same priority group.
+    /// Other systems in the group still run in parallel with both of them.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn physics(_: &World) {}
+    /// fn collisions(_: &World) {}
+    ///
+    /// World::new()
+    ///     .add_system(DefaultOrdering::Run, physics)
+    ///     .add_system_after(DefaultOrdering::Run, physics, collisions)
+    ///     .single_step();
+    /// ```
+    pub fn add_system_after<S: SystemOrdering + Copy>(&mut self, system_ordering: S, existing: SystemType, system: SystemType) -> &mut Self {
+        self.schedule.add_system_after(system_ordering, existing, system);
+        self
     }
 
-    /// Prints out a list of all resources
-    pub fn list_resources(&self) {
-        for resource in self.resources.iter() {
-            println!("{:#?}", resource);
-        }
+    /// Adds a system that runs before `existing` starts, in the same priority group.
+    /// Other systems in the group still run in parallel with both of them.
+    pub fn add_system_before<S: SystemOrdering + Copy>(&mut self, system_ordering: S, existing: SystemType, system: SystemType) -> &mut Self {
+        self.schedule.add_system_before(system_ordering, existing, system);
+        self
     }
 
-    /// Gets components based on a given type `T` and returns a Read guard
+    /// Adds a system straight to a priority group, for code that doesn't know the `SystemOrdering` of the application
     ///
-    /// # Errors
-    /// Will return a `StarryError::ComponentNotFound` if components are not found
-    /// # Example
-    /// ```rs
+    /// ```
     /// use starry_ecs::World;
-    /// use starry_ecs::component::Component;
     /// use starry_ecs::systems::DefaultOrdering;
     ///
-    /// #[derive(Clone, Debug)]
-    /// struct TestComponent { x: i32 }
-    /// impl Component for TestComponent {}
+    /// fn scripted(_: &World) {}
     ///
-    /// fn test_system(world: &World) {
-    ///     let _resource = world.try_get_components::<TestResource>().unwrap();
-    /// }
+    /// let mut world = World::new();
+    /// world.add_system_at_priority(2, scripted);
     ///
-    /// World::new().add_system(DefaultOrdering::Run, test_system).add_component(TestResource { x: 0 }).add_component(TestResource { x: 1 });
+    /// assert_eq!(world.system_count_at(DefaultOrdering::Run), 1);
     /// ```
-    pub fn try_get_components<T: Component + 'static>(&self) -> Result<Vec<ComponentReadGuard<'_, T>>, StarryError> {
-        let id = TypeId::of::<T>();
-
-        let comps = self
-            .components
-            .iter()
-            .filter(|(_, t)| t == &id)
-            .map(|(v, _)| RwLockReadGuard::map(v.read(), |r| {
-                unsafe { &*(r as *const dyn Component as *const T) }
-            }))
-            .collect::<Vec<MappedRwLockReadGuard<'_, T>>>();
-
-        if comps.len() == 0 {
-            return Err(StarryError::ComponentNotFound(type_name::<T>()));
-        }
-
-        Ok(comps)
+    pub fn add_system_at_priority(&mut self, priority: i32, system: SystemType) -> &mut Self {
+        self.schedule.add_system_at_priority(priority, system);
+        self
     }
 
-    /// Same as `try_get_components` but unwraps the value
-    pub fn get_components<T: Component + 'static>(&self) -> Vec<ComponentReadGuard<'_, T>> {
-        self.try_get_components().unwrap()
-    }
-    
-    /// Gets components based on a given type `T` and returns a Write guard
+    /// Adds many systems to a priority group at once, they run in parallel like systems added with `add_system`
     ///
-    /// # Errors
-    /// Will return a `StarryError::ComponentNotFound` if components are not found
-    /// # Example
-    /// ```rs
+    /// ```
     /// use starry_ecs::World;
-    /// use starry_ecs::component::Component;
-    /// use starry_ecs::systems::DefaultOrdering;
     ///
-    /// #[derive(Clone, Debug)]
-    /// struct TestComponent { x: i32 }
-    /// impl Component for TestComponent {}
+    /// fn input(_: &World) {}
+    /// fn audio(_: &World) {}
     ///
-    /// fn test_system(world: &World) {
-    ///     let _resource = world.try_get_components_mut::<TestResource>().unwrap();
-    /// }
+    /// let mut world = World::new();
+    /// world.add_system_group(2, vec![input, audio]);
     ///
-    /// World::new().add_system(DefaultOrdering::Run, test_system).add_component(TestResource { x: 0 }).add_component(TestResource { x: 1 });
+    /// assert_eq!(world.system_count(), 2);
     /// ```
-    pub fn try_get_components_mut<T: Component + 'static>(&self) -> Result<Vec<ComponentWriteGuard<'_, T>>, StarryError> {
-        let id = TypeId::of::<T>();
-
-        let comps = self
-            .components
-            .iter()
-            .filter(|(_, t)| t == &id)
-            .map(|(v, _)| RwLockWriteGuard::map(v.write(), |r| {
-                unsafe { &mut *(r as *mut dyn Component as *mut T) }
-            }))
-            .collect::<Vec<MappedRwLockWriteGuard<'_, T>>>();
-
-        if comps.len() == 0 {
-            return Err(StarryError::ComponentNotFound(type_name::<T>()));
-        }
+    pub fn add_system_group(&mut self, priority: i32, systems: Vec<SystemType>) -> &mut Self {
+        self.schedule.add_system_group(priority, systems);
+        self
+    }
 
-        Ok(comps)
+    /// Same as `add_system_group` but each system runs after the one before it in `systems`,
+    /// other systems in the priority group still run in parallel with them
+    pub fn add_system_group_sorted(&mut self, priority: i32, systems: Vec<SystemType>) -> &mut Self {
+        self.schedule.add_system_group_sorted(priority, systems);
+        self
     }
 
-    /// Same as `try_get_components_mut` but unwraps the value
-    pub fn get_components_mut<T: Component + 'static>(&self) -> Vec<ComponentWriteGuard<'_, T>> {
-        self.try_get_components_mut().unwrap()
+    /// Removes the first occurrence of a system from the priority group of an ordering.
+    /// Returns `true` if the system was found.
+    /// Once the last occurrence is gone, the system's dependencies, labels, description and run conditions are dropped too.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn ai(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_system(DefaultOrdering::Run, ai);
+    ///
+    /// assert!(world.remove_system(DefaultOrdering::Run, ai));
+    /// assert!(!world.remove_system(DefaultOrdering::Run, ai));
+    /// ```
+    pub fn remove_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: SystemType) -> bool {
+        self.schedule.remove_system(system_ordering, system)
     }
 
-    /// Runs a single step of the systems
+    /// Same as `add_system` but gives the system a label so it can be removed with `remove_system_by_label`.
+    /// Using a label again points it at the new system, the old system is left in place.
     ///
     /// ```
     /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
     ///
-    /// World::new().single_step();
+    /// fn debug_overlay(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_system_labeled("debug_overlay", DefaultOrdering::PostRun, debug_overlay);
+    ///
+    /// assert!(world.remove_system_by_label("debug_overlay"));
+    /// assert!(!world.has_system(debug_overlay));
     /// ```
-    pub fn single_step(&mut self) -> &mut Self {
-        let mut numbers = self.systems.iter().map(|(i, _)| i).collect::<Vec<_>>();
-        numbers.sort();
-        
-        let _ = numbers.iter().map(|system_group| {
-            let _ = self.systems.get(system_group).unwrap().par_iter().map(|system| system(&self)).collect::<Vec<_>>();
-        }).collect::<Vec<_>>();
+    pub fn add_system_labeled<S: SystemOrdering + Copy>(&mut self, label: &'static str, system_ordering: S, system: SystemType) -> &mut Self {
+        self.schedule.add_system_labeled(label, system_ordering, system);
         self
     }
 
-    /// Runs startup systems
+    /// Removes the system added with a label by `add_system_labeled`, returns `false` if no system has the label
+    pub fn remove_system_by_label(&mut self, label: &'static str) -> bool {
+        self.schedule.remove_system_by_label(label)
+    }
+
+    /// Adds an exclusive system with an ordering to the world.
+    /// Exclusive systems get mutable access to the world, so they run one after another
+    /// once every parallel system in their priority group has finished.
     ///
     /// ```
+    /// use starry_ecs::systems::DefaultOrdering;
     /// use starry_ecs::World;
     ///
-    /// World::new().start();
+    /// fn exclusive_system(world: &mut World) {
+    ///     world.add_startup_system(|_| println!("Hello, world!"));
+    /// }
+    ///
+    /// World::new().add_exclusive_system(DefaultOrdering::PostRun, exclusive_system).single_step();
     /// ```
-    pub fn start(&mut self) -> &mut Self {
-        let _ = self.starting_systems.par_iter().map(|system| system(&self)).collect::<Vec<_>>();
+    pub fn add_exclusive_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: ExclusiveSystemType) -> &mut Self {
+        self.schedule.add_exclusive_system(system_ordering, system);
         self
     }
 
-    /// Runs systems
+    /// Adds a named stage that systems can be added to with `add_system_to_stage`.
+    /// Adding a stage with an existing name changes its priority for systems added afterwards.
     ///
-    /// ```no_run
+    /// ```
     /// use starry_ecs::World;
     ///
-    /// World::new().run();
+    /// fn physics(_: &World) {
+    ///     println!("Physics!");
+    /// }
+    ///
+    /// World::new().add_stage("physics", 15).add_system_to_stage("physics", physics).single_step();
     /// ```
-    pub fn run(&mut self) -> ! {
-        loop {
-        let mut numbers = self.systems.iter().map(|(i, _)| i).collect::<Vec<_>>();
-        numbers.sort();
-        
-        let _ = numbers.iter().map(|system_group| {
-            let _ = self.systems.get(system_group).unwrap().par_iter().map(|system| system(&self)).collect::<Vec<_>>();
-        }).collect::<Vec<_>>();
-        }
+    pub fn add_stage(&mut self, name: &'static str, priority: i32) -> &mut Self {
+        self.stages.insert(name, priority);
+        self
     }
-}
\ No newline at end of file
+
+    /// Gets a stage added with `add_stage` by its name
+    pub fn stage(&self, name: &'static str) -> Option<Stage> {
+        self.stages.get(name).map(|&priority| Stage { name, priority })
+    }
+
+    /// Adds a system to a stage added with `add_stage`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::StageNotFound` if no stage has the given name
+    pub fn try_add_system_to_stage(&mut self, name: &'static str, system: SystemType) -> Result<&mut Self, StarryError> {
+        let stage = self.stage(name).ok_or(StarryError::StageNotFound(name))?;
+        Ok(self.add_system(stage, system))
+    }
+
+    /// Same as `try_add_system_to_stage` but unwraps the value
+    pub fn add_system_to_stage(&mut self, name: &'static str, system: SystemType) -> &mut Self {
+        self.try_add_system_to_stage(name, system).unwrap()
+    }
+
+    /// Adds a staring system
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// fn only_ran_once(_: &World) {
+    ///     println!("Hello, World!");
+    /// }
+    ///
+    /// World::new().add_startup_system(only_ran_once).start();
+    /// ```
+    pub fn add_startup_system(&mut self, system: SystemType) -> &mut Self {
+        self.schedule.add_startup_system(system);
+        self
+    }
+
+    /// Declares what a system reads and writes so `validate_no_conflicts` can check it
+    pub fn describe_system(&mut self, system: SystemType, meta: SystemMeta) -> &mut Self {
+        self.schedule.describe_system(system, meta);
+        self
+    }
+
+    /// Lists every pair of described systems that can run in parallel while accessing the same type,
+    /// where at least one of them writes to it. Only systems described with `describe_system` are checked,
+    /// nothing is enforced while systems run.
+    pub fn validate_no_conflicts(&self) -> Vec<String> {
+        self.schedule.validate_no_conflicts()
+    }
+
+    /// Adds a starting system that only runs if `condition` returns `true` when `start` is called.
+    /// The condition doesn't get the world, since it's checked before the world is fully set up.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// fn load_assets(_: &World) {
+    ///     println!("Loading assets");
+    /// }
+    ///
+    /// fn not_testing() -> bool {
+    ///     std::env::var("STARRY_TEST").is_err()
+    /// }
+    ///
+    /// World::new().add_startup_system_with_condition(load_assets, not_testing).start();
+    /// ```
+    pub fn add_startup_system_with_condition(&mut self, system: SystemType, condition: fn() -> bool) -> &mut Self {
+        self.schedule.add_startup_system_with_condition(system, condition);
+        self
+    }
+
+    /// Adds a starting system that gets mutable access to the world.
+    /// Exclusive startup systems run one after another once every other startup system has finished.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Config { players: usize }
+    /// impl Resource for Config {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Lobby { slots: Vec<Option<String>> }
+    /// impl Resource for Lobby {}
+    ///
+    /// fn create_lobby(world: &mut World) {
+    ///     let players = world.get_resource::<Config>().players;
+    ///     world.add_resource(Lobby { slots: vec![None; players] });
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Config { players: 4 }).add_exclusive_startup_system(create_lobby).start();
+    ///
+    /// assert_eq!(world.get_resource::<Lobby>().slots.len(), 4);
+    /// ```
+    pub fn add_exclusive_startup_system(&mut self, system: ExclusiveSystemType) -> &mut Self {
+        self.schedule.add_exclusive_startup_system(system);
+        self
+    }
+
+    /// Adds a system that runs once, at the beginning of the first step after `start`,
+    /// before any other system in that step. Useful for setup that needs every startup system to have finished.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// fn validate_setup(_: &World) {
+    ///     println!("Everything is loaded");
+    /// }
+    ///
+    /// World::new().add_system_after_startup(validate_setup).start().single_step().single_step();
+    /// ```
+    pub fn add_system_after_startup(&mut self, system: SystemType) -> &mut Self {
+        self.schedule.add_system_after_startup(system);
+        self
+    }
+
+    /// Removes the first occurrence of a starting system, returns `false` if it wasn't added.
+    /// Has no effect on startup systems that already ran.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// fn build_cache(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_startup_system(build_cache);
+    ///
+    /// assert!(world.remove_startup_system(build_cache));
+    /// assert!(!world.remove_startup_system(build_cache));
+    /// ```
+    pub fn remove_startup_system(&mut self, system: SystemType) -> bool {
+        self.schedule.remove_startup_system(system)
+    }
+
+    /// Describes the order systems run in, one priority group at a time.
+    /// Systems are named by their `SystemMeta` if they were described with `describe_system`.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::{DefaultOrdering, SystemMeta};
+    ///
+    /// fn physics(_: &World) {}
+    /// fn render(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .add_system(DefaultOrdering::Run, physics)
+    ///     .add_system(DefaultOrdering::PostRun, render)
+    ///     .describe_system(physics, SystemMeta::new("physics"))
+    ///     .describe_system(render, SystemMeta::new("render"));
+    ///
+    /// assert_eq!(world.system_execution_order(), "Priority group 2\n    runs [physics] in parallel\nPriority group 3\n    runs [render] in parallel\n");
+    /// ```
+    pub fn system_execution_order(&self) -> String {
+        self.schedule.system_execution_order()
+    }
+
+    /// Prints out the order systems run in, see `system_execution_order`
+    pub fn log_system_execution_order(&self) {
+        print!("{}", self.system_execution_order());
+    }
+
+    /// Removes every system and exclusive system, along with their dependencies and metadata.
+    /// Components and resources are left alone, so systems can be registered again for hot reloading.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn script(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_system(DefaultOrdering::Run, script).add_startup_system(script);
+    ///
+    /// world.clear_systems().clear_startup_systems();
+    /// assert_eq!(world.system_count(), 0);
+    /// assert_eq!(world.startup_system_count(), 0);
+    /// ```
+    pub fn clear_systems(&mut self) -> &mut Self {
+        self.schedule.clear_systems();
+        if let Ok(mut frame_count) = self.try_get_resource_mut::<FrameCount>() {
+            frame_count.since_start = 0;
+        }
+        self
+    }
+
+    /// Removes every startup system, along with systems added with `add_system_after_startup`
+    pub fn clear_startup_systems(&mut self) -> &mut Self {
+        self.schedule.clear_startup_systems();
+        self
+    }
+
+    /// Counts the systems added with `add_system` across every priority group
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn first(_: &World) {}
+    /// fn second(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_system(DefaultOrdering::PreRun, first).add_system(DefaultOrdering::Run, second).add_startup_system(first);
+    ///
+    /// assert_eq!(world.system_count(), 2);
+    /// assert_eq!(world.system_count_at(DefaultOrdering::Run), 1);
+    /// assert_eq!(world.startup_system_count(), 1);
+    /// ```
+    pub fn system_count(&self) -> usize {
+        self.schedule.system_count()
+    }
+
+    /// Counts the systems added with `add_system` at a certain ordering
+    pub fn system_count_at<S: SystemOrdering + Copy>(&self, system_ordering: S) -> usize {
+        self.schedule.system_count_at(system_ordering)
+    }
+
+    /// Lists every priority group that has systems or exclusive systems, in the order they run
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn render(_: &World) {}
+    /// fn input(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_system(DefaultOrdering::PostRun, render).add_system_at_priority(-1, input);
+    ///
+    /// assert_eq!(world.system_priorities(), vec![-1, 3]);
+    /// assert_eq!(world.system_count_at_priority(-1), 1);
+    /// ```
+    pub fn system_priorities(&self) -> Vec<i32> {
+        self.schedule.system_priorities()
+    }
+
+    /// Counts the systems in a priority group, exclusive systems aren't counted
+    pub fn system_count_at_priority(&self, priority: i32) -> usize {
+        self.schedule.system_count_at_priority(priority)
+    }
+
+    /// Counts the systems added with `add_startup_system`
+    pub fn startup_system_count(&self) -> usize {
+        self.schedule.startup_system_count()
+    }
+
+    /// Whether a system was added at any ordering, so plugins can avoid adding a system twice
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn physics(_: &World) {}
+    ///
+    /// let mut world = World::new();
+    /// if !world.has_system(physics) {
+    ///     world.add_system(DefaultOrdering::Run, physics);
+    /// }
+    ///
+    /// assert!(world.has_system(physics));
+    /// assert!(!world.has_startup_system(physics));
+    /// ```
+    pub fn has_system(&self, system: SystemType) -> bool {
+        self.schedule.has_system(system)
+    }
+
+    /// Whether a system was added with `add_startup_system`
+    pub fn has_startup_system(&self, system: SystemType) -> bool {
+        self.schedule.has_startup_system(system)
+    }
+
+    /// Adds a resource to the world.
+    /// There can only be once instance of each resource.
+    /// If an existing resource exists, it will not be replaced.
+    ///
+    /// ```
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::World;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// pub struct TestResource { x: i32 }
+    /// impl Resource for TestResource {}
+    /// 
+    /// World::new().add_resource(TestResource { x: 0 });
+    /// ```
+    pub fn add_resource<T: Resource + 'static>(&mut self, resource: T) -> &mut Self {
+        self.resources.entry(TypeId::of::<T>()).or_insert(Arc::new(RwLock::new(resource)));
+        self.register_resource_type::<T>();
+        self
+    }
+
+    /// Same as `add_resource` but only calls `f` to create the resource if it doesn't exist yet
+    ///
+    /// ```
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::World;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// pub struct Assets { loaded: usize }
+    /// impl Resource for Assets {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Assets { loaded: 1 }).add_resource_with(|| -> Assets { unreachable!() });
+    ///
+    /// assert_eq!(world.get_resource::<Assets>().loaded, 1);
+    /// ```
+    pub fn add_resource_with<T: Resource + 'static>(&mut self, f: impl FnOnce() -> T) -> &mut Self {
+        self.resources.entry(TypeId::of::<T>()).or_insert_with(|| Arc::new(RwLock::new(f())));
+        self.register_resource_type::<T>();
+        self
+    }
+
+    /// Adds a resource wrapped in a `TrackedResource`, so `resource_changed` can tell whether it was mutated during a step.
+    /// The resource is accessed as a `TrackedResource<T>` and every tracked resource is marked unchanged at the end of each step.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::{Resource, TrackedResource};
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Settings { volume: u8 }
+    /// impl Resource for Settings {}
+    ///
+    /// fn change_volume(world: &World) {
+    ///     world.get_resource_mut::<TrackedResource<Settings>>().volume = 5;
+    /// }
+    ///
+    /// fn apply_settings(world: &World) {
+    ///     if world.resource_changed::<Settings>() {
+    ///         println!("Volume is now {}", world.get_resource::<TrackedResource<Settings>>().volume);
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .add_resource_tracked(Settings { volume: 10 })
+    ///     .add_system(DefaultOrdering::Run, change_volume)
+    ///     .add_system(DefaultOrdering::PostRun, apply_settings)
+    ///     .single_step();
+    ///
+    /// assert!(!world.resource_changed::<Settings>());
+    /// ```
+    pub fn add_resource_tracked<T: Resource + Clone + 'static>(&mut self, resource: T) -> &mut Self {
+        self.change_trackers.insert(TypeId::of::<T>(), resources::clear_changed::<T>);
+        self.add_resource(TrackedResource::new(resource))
+    }
+
+    /// Whether the resource added with `add_resource_tracked` was mutated during the current step,
+    /// returns `false` if there's no tracked resource of type `T`
+    pub fn resource_changed<T: Resource + Clone + 'static>(&self) -> bool {
+        self.try_get_resource::<TrackedResource<T>>().is_ok_and(|resource| resource.changed())
+    }
+
+    /// Adds a resource wrapped in a `ReactiveResource`, so functions added with `subscribe_resource`
+    /// are called whenever it's changed through `get_reactive_resource_mut`
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Volume(u8);
+    /// impl Resource for Volume {}
+    ///
+    /// fn update_mixer(volume: &Volume) {
+    ///     println!("Volume changed to {}", volume.0);
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_reactive_resource(Volume(10)).subscribe_resource(update_mixer);
+    ///
+    /// world.get_reactive_resource_mut::<Volume>().0 = 5;
+    /// ```
+    pub fn add_reactive_resource<T: Resource + Clone + 'static>(&mut self, resource: T) -> &mut Self {
+        self.add_resource(ReactiveResource::new(resource))
+    }
+
+    /// Adds a function to call with the new value of the resource added with `add_reactive_resource` after every change
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if there's no reactive resource of type `T`
+    pub fn try_subscribe_resource<T: Resource + Clone + 'static>(&mut self, callback: fn(&T)) -> Result<&mut Self, StarryError> {
+        self.try_get_resource_mut::<ReactiveResource<T>>()?.subscribe(callback);
+        Ok(self)
+    }
+
+    /// Same as `try_subscribe_resource` but unwraps the value
+    pub fn subscribe_resource<T: Resource + Clone + 'static>(&mut self, callback: fn(&T)) -> &mut Self {
+        self.try_subscribe_resource(callback).unwrap()
+    }
+
+    /// Gets the resource added with `add_reactive_resource` and returns a Write guard
+    /// that calls its subscribers when dropped, if the resource was mutably accessed
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if there's no reactive resource of type `T`
+    pub fn try_get_reactive_resource_mut<T: Resource + Clone + 'static>(&self) -> Result<ReactiveWriteGuard<'_, T>, StarryError> {
+        Ok(ReactiveWriteGuard::new(self.try_get_resource_mut::<ReactiveResource<T>>()?))
+    }
+
+    /// Same as `try_get_reactive_resource_mut` but unwraps the value
+    pub fn get_reactive_resource_mut<T: Resource + Clone + 'static>(&self) -> ReactiveWriteGuard<'_, T> {
+        self.try_get_reactive_resource_mut().unwrap()
+    }
+
+    /// Adds a resource that's created from the rest of the world once `initialize_resources` is called,
+    /// for resources that depend on other resources
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Device { name: String }
+    /// impl Resource for Device {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Pipeline { device: String }
+    /// impl Resource for Pipeline {}
+    ///
+    /// fn create_pipeline(world: &World) -> Pipeline {
+    ///     Pipeline { device: world.get_resource::<Device>().name.clone() }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .add_resource_initializer(create_pipeline)
+    ///     .add_resource(Device { name: "gpu".to_string() })
+    ///     .initialize_resources();
+    ///
+    /// assert_eq!(world.get_resource::<Pipeline>().device, "gpu");
+    /// ```
+    pub fn add_resource_initializer<T: Resource + 'static>(&mut self, f: fn(&World) -> T) -> &mut Self {
+        self.resource_initializers.push(Arc::new(move |world: &mut World| {
+            let resource = f(world);
+            world.add_resource(resource);
+        }));
+        self
+    }
+
+    /// Creates every resource added with `add_resource_initializer`, in the order they were added,
+    /// so initializers can use resources created by the ones before them. Each initializer only runs once.
+    ///
+    /// # Panics
+    /// Panics if an initializer panics, for example by getting a resource that doesn't exist
+    pub fn initialize_resources(&mut self) -> &mut Self {
+        for initializer in std::mem::take(&mut self.resource_initializers) {
+            initializer(self);
+        }
+        self
+    }
+
+    /// Same as `add_resource` with the default value of `T`
+    ///
+    /// ```
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::World;
+    ///
+    /// #[derive(Clone, Debug, Default)]
+    /// pub struct Score { points: u32 }
+    /// impl Resource for Score {}
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource_default::<Score>();
+    ///
+    /// assert_eq!(world.get_resource::<Score>().points, 0);
+    /// ```
+    pub fn insert_resource_default<T: Resource + Default + 'static>(&mut self) -> &mut Self {
+        self.add_resource_with(T::default)
+    }
+    
+    /// Shares the resource of type `T` under type `U` as well, so both types lock the same data.
+    /// If a resource of type `U` already exists, it will not be replaced.
+    /// Snapshots and clones copy the data once and keep both types pointing at the copy.
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if there's no resource of type `T`
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::{Resource, ResourceAlias};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct WindowSize { width: u32, height: u32 }
+    /// impl Resource for WindowSize {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// #[repr(transparent)]
+    /// struct ScreenDimensions(WindowSize);
+    /// impl Resource for ScreenDimensions {}
+    /// unsafe impl ResourceAlias<WindowSize> for ScreenDimensions {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(WindowSize { width: 800, height: 600 }).add_resource_alias::<WindowSize, ScreenDimensions>();
+    ///
+    /// world.get_resource_mut::<WindowSize>().width = 1024;
+    /// assert_eq!(world.get_resource::<ScreenDimensions>().0.width, 1024);
+    /// ```
+    pub fn try_add_resource_alias<T: Resource + 'static, U: ResourceAlias<T> + 'static>(&mut self) -> Result<&mut Self, StarryError> {
+        let resource = self.resources.get(&TypeId::of::<T>()).ok_or(StarryError::ResourceNotFound(type_name::<T>()))?.clone();
+        if let Entry::Vacant(entry) = self.resources.entry(TypeId::of::<U>()) {
+            entry.insert(resource);
+            self.resource_aliases.insert(TypeId::of::<U>(), self.canonical_resource_id(TypeId::of::<T>()));
+        }
+        self.register_resource_type::<U>();
+        Ok(self)
+    }
+
+    /// The type a resource was first added as, following aliases from `add_resource_alias`
+    pub(crate) fn canonical_resource_id(&self, id: TypeId) -> TypeId {
+        self.resource_aliases.get(&id).copied().unwrap_or(id)
+    }
+
+    /// Same as `try_add_resource_alias` but unwraps the value
+    pub fn add_resource_alias<T: Resource + 'static, U: ResourceAlias<T> + 'static>(&mut self) -> &mut Self {
+        self.try_add_resource_alias::<T, U>().unwrap()
+    }
+
+    /// Gets a resource based on a given type `T` and returns a Read guard
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// # Example
+    /// ```rs
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestResource { x: i32 }
+    /// impl Resource for TestResource {}
+    ///
+    /// fn test_system(world: &World) {
+    ///     let _resource = world.try_get_resource::<TestResource>().unwrap();
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, test_system).add_resource(TestResource { x: 0 });
+    /// ```
+    pub fn try_get_resource<T: Resource + 'static>(&self) -> Result<ResourceReadGuard<'_, T>, StarryError> {
+        let name = TypeId::of::<T>();
+        let cloned = match self.resources.get(&name) {
+            Some(ok) => ok,
+            None => return Err(StarryError::ResourceNotFound(type_name::<T>()))
+        };
+        Ok(RwLockReadGuard::map(cloned.read(), |r| {
+            unsafe { &*(r as *const dyn Resource as *const T) }
+        }))
+    }
+
+    /// Same as `try_get_resource` but unwraps the value
+    pub fn get_resource<T: Resource + 'static>(&self) -> ResourceReadGuard<'_, T> {
+        self.try_get_resource::<T>().unwrap()
+    }
+
+    /// Gets a resource based on a given type `T` and returns a Write guard
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// # Example
+    /// ```rs
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestResource { x: i32 }
+    /// impl Resource for TestResource {}
+    ///
+    /// fn test_system(world: &World) {
+    ///     let _resource = world.try_get_resource_mut::<TestResource>().unwrap();
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, test_system).add_resource(TestResource { x: 0 });
+    /// ```
+    pub fn try_get_resource_mut<T: Resource + 'static>(&self) -> Result<ResourceWriteGuard<'_, T>, StarryError> {
+        let name = TypeId::of::<T>();
+        let cloned = match self.resources.get(&name) {
+            Some(ok) => ok,
+            None => return Err(StarryError::ResourceNotFound(type_name::<T>()))
+        };
+        Ok(RwLockWriteGuard::map(cloned.write(), |r| {
+            unsafe { &mut *(&mut *r as *mut dyn Resource as *mut T) }
+        }))
+    }
+
+    /// Same as `try_get_resource_mut` but unwraps the value
+    pub fn get_resource_mut<T: Resource + 'static>(&self) -> ResourceWriteGuard<'_, T> {
+        self.try_get_resource_mut::<T>().unwrap()
+    }
+
+    /// Same as `try_get_resource` but gives up if the lock isn't acquired within `timeout`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// and a `StarryError::LockTimeout` if the resource stays write locked for longer than `timeout`
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use starry_ecs::{StarryError, World};
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestResource { x: i32 }
+    /// impl Resource for TestResource {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(TestResource { x: 0 });
+    ///
+    /// let _writer = world.get_resource_mut::<TestResource>();
+    /// let result = world.try_get_resource_timeout::<TestResource>(Duration::from_millis(10));
+    /// assert!(matches!(result, Err(StarryError::LockTimeout(_))));
+    /// ```
+    pub fn try_get_resource_timeout<T: Resource + 'static>(&self, timeout: Duration) -> Result<ResourceReadGuard<'_, T>, StarryError> {
+        let resource = self.resources.get(&TypeId::of::<T>()).ok_or(StarryError::ResourceNotFound(type_name::<T>()))?;
+        let guard = resource.try_read_for(timeout).ok_or(StarryError::LockTimeout(type_name::<T>()))?;
+
+        Ok(RwLockReadGuard::map(guard, |r| {
+            unsafe { &*(r as *const dyn Resource as *const T) }
+        }))
+    }
+
+    /// Same as `try_get_resource_mut` but gives up if the lock isn't acquired within `timeout`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// and a `StarryError::LockTimeout` if the resource stays locked for longer than `timeout`
+    pub fn try_get_resource_mut_timeout<T: Resource + 'static>(&self, timeout: Duration) -> Result<ResourceWriteGuard<'_, T>, StarryError> {
+        let resource = self.resources.get(&TypeId::of::<T>()).ok_or(StarryError::ResourceNotFound(type_name::<T>()))?;
+        let guard = resource.try_write_for(timeout).ok_or(StarryError::LockTimeout(type_name::<T>()))?;
+
+        Ok(RwLockWriteGuard::map(guard, |r| {
+            unsafe { &mut *(r as *mut dyn Resource as *mut T) }
+        }))
+    }
+
+    /// Gets two resources and returns a Write guard for each.
+    /// The locks are always taken in the same order no matter the order of `A` and `B`,
+    /// so two systems asking for the same pair can't deadlock each other.
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if either resource is not found, no locks are taken in that case
+    /// # Panics
+    /// Panics if `A` and `B` are the same type or aliases of the same resource
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Gold(u32);
+    /// impl Resource for Gold {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Inventory { swords: u32 }
+    /// impl Resource for Inventory {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Gold(10)).add_resource(Inventory { swords: 0 });
+    ///
+    /// let (mut gold, mut inventory) = world.resource_mut_pair::<Gold, Inventory>();
+    /// gold.0 -= 10;
+    /// inventory.swords += 1;
+    /// ```
+    pub fn try_resource_mut_pair<A: Resource + 'static, B: Resource + 'static>(&self) -> Result<(ResourceWriteGuard<'_, A>, ResourceWriteGuard<'_, B>), StarryError> {
+        let (a, b) = self.lock_resource_pair::<A, B, _>(RwLock::write)?;
+
+        Ok((
+            RwLockWriteGuard::map(a, |r| unsafe { &mut *(r as *mut dyn Resource as *mut A) }),
+            RwLockWriteGuard::map(b, |r| unsafe { &mut *(r as *mut dyn Resource as *mut B) })
+        ))
+    }
+
+    /// Finds resources `A` and `B` and locks both with `lock`, always taking the locks in the order of their canonical `TypeId`.
+    /// No locks are taken if either resource is missing.
+    ///
+    /// # Panics
+    /// Panics if `A` and `B` are the same type or aliases of the same resource
+    fn lock_resource_pair<'a, A: Resource + 'static, B: Resource + 'static, G>(&'a self, lock: impl Fn(&'a RwLock<dyn Resource>) -> G) -> Result<(G, G), StarryError> {
+        let a_id = self.canonical_resource_id(TypeId::of::<A>());
+        let b_id = self.canonical_resource_id(TypeId::of::<B>());
+        assert!(a_id != b_id, "Can't lock resource `{}` twice", type_name::<A>());
+        let a = self.resources.get(&TypeId::of::<A>()).ok_or(StarryError::ResourceNotFound(type_name::<A>()))?;
+        let b = self.resources.get(&TypeId::of::<B>()).ok_or(StarryError::ResourceNotFound(type_name::<B>()))?;
+
+        Ok(if a_id < b_id {
+            let a = lock(a);
+            (a, lock(b))
+        } else {
+            let b = lock(b);
+            (lock(a), b)
+        })
+    }
+
+    /// Same as `try_resource_mut_pair` but unwraps the value
+    pub fn resource_mut_pair<A: Resource + 'static, B: Resource + 'static>(&self) -> (ResourceWriteGuard<'_, A>, ResourceWriteGuard<'_, B>) {
+        self.try_resource_mut_pair::<A, B>().unwrap()
+    }
+
+    /// Gets two resources and calls `f` with read access to both, releasing both locks once `f` returns.
+    /// Like `try_resource_mut_pair` the locks are always taken in the same order no matter the order of `A` and `B`.
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if either resource is not found, `f` isn't called in that case
+    /// # Panics
+    /// Panics if `A` and `B` are the same type or aliases of the same resource
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Gold(u32);
+    /// impl Resource for Gold {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Prices { sword: u32 }
+    /// impl Resource for Prices {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Gold(10)).add_resource(Prices { sword: 8 });
+    ///
+    /// let affordable = world.query_resource_chain(|gold: &Gold, prices: &Prices| gold.0 >= prices.sword);
+    /// assert!(affordable);
+    /// ```
+    pub fn try_query_resource_chain<A: Resource + 'static, B: Resource + 'static, R>(&self, f: impl FnOnce(&A, &B) -> R) -> Result<R, StarryError> {
+        let (a, b) = self.lock_resource_pair::<A, B, _>(RwLock::read)?;
+
+        Ok(f(
+            unsafe { &*(&*a as *const dyn Resource as *const A) },
+            unsafe { &*(&*b as *const dyn Resource as *const B) }
+        ))
+    }
+
+    /// Same as `try_query_resource_chain` but unwraps the value
+    pub fn query_resource_chain<A: Resource + 'static, B: Resource + 'static, R>(&self, f: impl FnOnce(&A, &B) -> R) -> R {
+        self.try_query_resource_chain(f).unwrap()
+    }
+
+    /// Gets a resource based on a given type `T` and calls `f` with mutable access to it.
+    /// The write lock is released as soon as `f` returns.
+    ///
+    /// # Panics
+    /// Panics if the resource is not found
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestResource { x: i32 }
+    /// impl Resource for TestResource {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(TestResource { x: 0 });
+    ///
+    /// let x = world.resource_scope(|resource: &mut TestResource| {
+    ///     resource.x += 1;
+    ///     resource.x
+    /// });
+    /// assert_eq!(x, 1);
+    /// ```
+    pub fn resource_scope<T: Resource + 'static, R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.get_resource_mut::<T>())
+    }
+
+    /// Gets a resource based on a given type `T` and calls `f` with read access to it.
+    /// The read lock is released as soon as `f` returns, so it can't be held by accident.
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestResource { x: i32 }
+    /// impl Resource for TestResource {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(TestResource { x: 3 });
+    ///
+    /// assert_eq!(world.with_resource_borrow(|resource: &TestResource| resource.x * 2).unwrap(), 6);
+    /// ```
+    pub fn with_resource_borrow<T: Resource + 'static, R>(&self, f: impl FnOnce(&T) -> R) -> Result<R, StarryError> {
+        let resource = self.try_get_resource::<T>()?;
+        Ok(f(&resource))
+    }
+
+    /// Removes the resource of type `T` and gives it back.
+    /// Returns `None` and leaves the resource in place if it's missing
+    /// or shared with a cloned world or an alias from `add_resource_alias`.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Connection { port: u16 }
+    /// impl Resource for Connection {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Connection { port: 8080 });
+    ///
+    /// let shared = world.clone();
+    /// assert!(world.take_resource::<Connection>().is_none());
+    /// drop(shared);
+    ///
+    /// assert_eq!(world.take_resource::<Connection>().unwrap().port, 8080);
+    /// assert!(world.try_get_resource::<Connection>().is_err());
+    /// ```
+    pub fn take_resource<T: Resource + 'static>(&mut self) -> Option<T> {
+        let id = TypeId::of::<T>();
+        if Arc::strong_count(self.resources.get(&id)?) > 1 {
+            return None;
+        }
+
+        let resource = unsafe { resources::downcast_locked::<T>(self.resources.remove(&id)?) };
+        Arc::try_unwrap(resource).ok().map(RwLock::into_inner)
+    }
+
+    /// Whether the resource of type `T` is currently held by at least one read guard, for tracking down blocking.
+    /// `parking_lot` doesn't expose how many readers a lock has, only whether it's shared or exclusive.
+    /// Returns `false` if the resource was never added.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Physics { gravity: f32 }
+    /// impl Resource for Physics {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Physics { gravity: 9.8 });
+    ///
+    /// let physics = world.get_resource::<Physics>();
+    /// assert!(world.is_resource_read_locked::<Physics>());
+    /// assert!(!world.is_resource_write_locked::<Physics>());
+    /// drop(physics);
+    ///
+    /// let _physics = world.get_resource_mut::<Physics>();
+    /// assert!(world.is_resource_write_locked::<Physics>());
+    /// ```
+    pub fn is_resource_read_locked<T: Resource + 'static>(&self) -> bool {
+        self.resources.get(&TypeId::of::<T>()).is_some_and(|resource| resource.is_locked() && !resource.is_locked_exclusive())
+    }
+
+    /// Whether the resource of type `T` is currently held by a write guard, returns `false` if the resource was never added
+    pub fn is_resource_write_locked<T: Resource + 'static>(&self) -> bool {
+        self.resources.get(&TypeId::of::<T>()).is_some_and(|resource| resource.is_locked_exclusive())
+    }
+
+    /// Calls `Resource::tick` on every resource with the time passed since the last tick
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Cooldown { remaining: Duration }
+    /// impl Resource for Cooldown {
+    ///     fn tick(&mut self, delta: Duration) {
+    ///         self.remaining = self.remaining.saturating_sub(delta);
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Cooldown { remaining: Duration::from_secs(1) });
+    ///
+    /// world.tick_resources(Duration::from_millis(400));
+    /// assert_eq!(world.get_resource::<Cooldown>().remaining, Duration::from_millis(600));
+    /// ```
+    pub fn tick_resources(&self, delta: Duration) {
+        for (_, resource) in self.unaliased_resources() {
+            resource.write().tick(delta);
+        }
+    }
+
+    /// Prints out a list of all resources
+    pub fn list_resources(&self) {
+        for resource in self.unaliased_resources() {
+            println!("{:#?}", resource);
+        }
+    }
+
+    /// Iterates over every resource once, skipping the types added with `add_resource_alias`
+    pub(crate) fn unaliased_resources(&self) -> impl Iterator<Item = (&TypeId, &Arc<RwLock<dyn Resource>>)> {
+        self.resources.iter().filter(|(id, _)| !self.resource_aliases.contains_key(id))
+    }
+
+    /// Lists every component grouped by type, with the number of components of each type
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Position { x: 1 }).add_component(Position { x: 2 });
+    ///
+    /// assert!(world.list_components().ends_with("Position (2)\n    Position { x: 1 }\n    Position { x: 2 }\n"));
+    /// ```
+    pub fn list_components(&self) -> String {
+        let mut types: Vec<TypeId> = vec![];
+        for (_, t, _) in self.components.iter() {
+            if !types.contains(t) {
+                types.push(*t);
+            }
+        }
+
+        let mut list = String::new();
+        for id in types {
+            let components = self.components.iter().filter(|(_, t, _)| t == &id).collect::<Vec<_>>();
+            list.push_str(&format!("{} ({})\n", self.component_names[&id], components.len()));
+            for (component, _, _) in components {
+                list.push_str(&format!("    {:?}\n", &*component.read()));
+            }
+        }
+        list
+    }
+
+    /// Lists the type names of every component type in the world in alphabetical order, without duplicates.
+    /// Names are stored when a type is first added, so no locks are taken.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Gravity(f32);
+    /// impl Resource for Gravity {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Position { x: 1 }).add_component(Position { x: 2 }).add_resource(Gravity(9.8));
+    ///
+    /// assert_eq!(world.component_types().len(), 1);
+    /// assert!(world.component_types()[0].ends_with("Position"));
+    /// assert!(world.resource_types()[0].ends_with("Gravity"));
+    ///
+    /// world.take_resource::<Gravity>();
+    /// assert!(world.resource_types().is_empty());
+    /// ```
+    pub fn component_types(&self) -> Vec<&'static str> {
+        let mut types: Vec<TypeId> = vec![];
+        for (_, t, _) in self.components.iter() {
+            if !types.contains(t) {
+                types.push(*t);
+            }
+        }
+        let mut names = types.iter().map(|id| self.component_names[id]).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Lists the type names of every resource in the world in alphabetical order,
+    /// aliases from `add_resource_alias` are listed under their own name.
+    /// Names are stored when a type is first added, so no locks are taken.
+    pub fn resource_types(&self) -> Vec<&'static str> {
+        let mut names = self.resources.keys().map(|id| self.resource_names[id]).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Counts the resources in the world, aliases from `add_resource_alias` count separately
+    pub fn resource_count(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Estimates how much memory the components and resources take up by adding up their sizes.
+    /// Heap memory they own, like the contents of a `Vec`, isn't counted, so this is a lower bound.
+    /// Read locks are taken on one component or resource at a time.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: f64, y: f64 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component_batch(Position { x: 0.0, y: 0.0 }, 10);
+    ///
+    /// assert!(world.total_memory_estimate() >= 10 * std::mem::size_of::<Position>());
+    /// ```
+    pub fn total_memory_estimate(&self) -> usize {
+        let components = self.components.iter().map(|(component, _, _)| std::mem::size_of_val(&*component.read())).sum::<usize>();
+        let resources = self.unaliased_resources().map(|(_, resource)| std::mem::size_of_val(&*resource.read())).sum::<usize>();
+        components + resources
+    }
+
+    /// Iterates over every resource without knowing its type, for tools that inspect world state.
+    /// No locks are taken, the world can't be changed while the iterator is borrowed.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Score(u32);
+    /// impl Resource for Score {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Score(10));
+    ///
+    /// for (_, resource) in world.iter_resources() {
+    ///     assert_eq!(format!("{:?}", resource.read()), "Score(10)");
+    /// }
+    /// ```
+    pub fn iter_resources(&self) -> impl Iterator<Item = (&TypeId, &Arc<RwLock<dyn Resource>>)> {
+        self.resources.iter()
+    }
+
+    /// Same as `iter_resources` but for every component, in storage order
+    pub fn iter_raw_components(&self) -> impl Iterator<Item = (&TypeId, &Arc<RwLock<dyn Component>>)> {
+        self.components.iter().map(|(v, t, _)| (t, v))
+    }
+
+    /// Iterates over components of type `T`, only locking each component when it's reached
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestComponent { x: i32 }
+    /// impl Component for TestComponent {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(TestComponent { x: 1 }).add_component(TestComponent { x: 2 });
+    ///
+    /// assert_eq!(world.iter_components::<TestComponent>().map(|c| c.x).sum::<i32>(), 3);
+    /// ```
+    pub fn iter_components<T: Component + 'static>(&self) -> impl Iterator<Item = ComponentReadGuard<'_, T>> {
+        let id = TypeId::of::<T>();
+
+        self.components
+            .iter()
+            .filter(move |(_, t, _)| t == &id)
+            .map(|(v, _, _)| RwLockReadGuard::map(v.read(), |r| {
+                unsafe { &*(r as *const dyn Component as *const T) }
+            }))
+    }
+
+    /// Same as `iter_components` but returns Write guards. Each component is only locked when it's reached,
+    /// unlike `get_components_mut` which locks every component at once.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Velocity { x: i32 }
+    /// impl Component for Velocity {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Velocity { x: 1 }).add_component(Velocity { x: 2 });
+    ///
+    /// for mut velocity in world.components_as_iter_mut::<Velocity>() {
+    ///     velocity.x *= 2;
+    /// }
+    /// assert_eq!(world.iter_components::<Velocity>().map(|v| v.x).sum::<i32>(), 6);
+    /// ```
+    pub fn components_as_iter_mut<T: Component + 'static>(&self) -> impl Iterator<Item = ComponentWriteGuard<'_, T>> {
+        let id = TypeId::of::<T>();
+
+        self.components
+            .iter()
+            .filter(move |(_, t, _)| t == &id)
+            .map(|(v, _, _)| RwLockWriteGuard::map(v.write(), |r| {
+                unsafe { &mut *(r as *mut dyn Component as *mut T) }
+            }))
+    }
+
+    /// Calls `f` with mutable access to every component of type `T`, one at a time.
+    /// Each write lock is released before the next component is locked.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Position { x: 1 }).add_component(Position { x: 2 });
+    ///
+    /// world.apply_fn_to_components(|position: &mut Position| position.x *= 10);
+    /// assert_eq!(world.iter_components::<Position>().map(|p| p.x).collect::<Vec<_>>(), vec![10, 20]);
+    /// ```
+    pub fn apply_fn_to_components<T: Component + 'static>(&self, f: impl Fn(&mut T)) {
+        let id = TypeId::of::<T>();
+
+        for (v, _, _) in self.components.iter().filter(|(_, t, _)| t == &id) {
+            let mut guard = v.write();
+            f(unsafe { &mut *(&mut *guard as *mut dyn Component as *mut T) });
+        }
+    }
+
+    /// Same as `apply_fn_to_components` but calls `f` on many components in parallel.
+    /// Runs in the thread pool set with `with_thread_pool`, if there is one.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Position { x: 1 }).add_component(Position { x: 2 });
+    ///
+    /// world.par_apply_fn_to_components(|position: &mut Position| position.x += 1);
+    /// assert_eq!(world.iter_components::<Position>().map(|p| p.x).sum::<i32>(), 5);
+    /// ```
+    pub fn par_apply_fn_to_components<T: Component + Send + 'static>(&self, f: impl Fn(&mut T) + Send + Sync) {
+        let id = TypeId::of::<T>();
+
+        // Components aren't `Sync` on their own, so the world is shared with the threads instead
+        self.in_thread_pool(|| (0..self.components.len()).into_par_iter().filter(|i| self.components[*i].1 == id).for_each(|i| {
+            let mut guard = self.components[i].0.write();
+            f(unsafe { &mut *(&mut *guard as *mut dyn Component as *mut T) });
+        }));
+    }
+
+    /// Removes every component of type `T` that `predicate` returns `false` for, keeping the order of the rest
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Projectile { health: i32 }
+    /// impl Component for Projectile {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Projectile { health: 0 }).add_component(Projectile { health: 3 });
+    ///
+    /// world.retain_components(|projectile: &Projectile| projectile.health > 0);
+    /// assert_eq!(world.get_components::<Projectile>().len(), 1);
+    /// ```
+    pub fn retain_components<T: Component + 'static>(&mut self, predicate: impl Fn(&T) -> bool) -> &mut Self {
+        let id = TypeId::of::<T>();
+
+        let removed = self.components.iter().map(|(v, t, _)| {
+            t == &id && !predicate(unsafe { &*(&*v.read() as *const dyn Component as *const T) })
+        }).collect::<Vec<_>>();
+
+        let mut removed = removed.into_iter();
+        let taken = self.remove_components(|_| removed.next().unwrap());
+        self.rebuild_entity_index();
+        self.notify_removed(&taken);
+        self
+    }
+
+    /// Sorts the components of type `T`, see `sort_components_by`
+    pub fn sort_components<T: Component + Ord + 'static>(&mut self) -> &mut Self {
+        self.sort_components_by::<T>(T::cmp)
+    }
+
+    /// Stably sorts the components of type `T` with `compare`, so iterating over them follows that order.
+    /// Components of other types keep their place.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Sprite { z: i32 }
+    /// impl Component for Sprite {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Sprite { z: 2 }).add_component(Sprite { z: 0 }).add_component(Sprite { z: 1 });
+    ///
+    /// world.sort_components_by(|a: &Sprite, b: &Sprite| a.z.cmp(&b.z));
+    /// assert_eq!(world.iter_components::<Sprite>().map(|s| s.z).collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// ```
+    pub fn sort_components_by<T: Component + 'static>(&mut self, compare: impl Fn(&T, &T) -> Ordering) -> &mut Self {
+        let id = TypeId::of::<T>();
+
+        let positions = self.components.iter().enumerate().filter(|(_, (_, t, _))| t == &id).map(|(i, _)| i).collect::<Vec<_>>();
+        let mut sorted = positions.iter().map(|i| self.components[*i].clone()).collect::<Vec<_>>();
+        sorted.sort_by(|(a, _, _), (b, _, _)| {
+            let (a, b) = (a.read(), b.read());
+            compare(
+                unsafe { &*(&*a as *const dyn Component as *const T) },
+                unsafe { &*(&*b as *const dyn Component as *const T) }
+            )
+        });
+
+        for (position, component) in positions.into_iter().zip(sorted) {
+            self.components[position] = component;
+        }
+        self.rebuild_entity_index();
+        self
+    }
+
+    /// Swaps the `i`th and `j`th components of type `T`, counting only components of type `T`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if either index is out of range, nothing is swapped in that case
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Sprite { z: i32 }
+    /// impl Component for Sprite {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Sprite { z: 0 }).add_component(Sprite { z: 1 }).add_component(Sprite { z: 2 });
+    ///
+    /// world.swap_components::<Sprite>(0, 2);
+    /// assert_eq!(world.iter_components::<Sprite>().map(|s| s.z).collect::<Vec<_>>(), vec![2, 1, 0]);
+    /// ```
+    pub fn try_swap_components<T: Component + 'static>(&mut self, i: usize, j: usize) -> Result<&mut Self, StarryError> {
+        let positions = self.component_index_of::<T>();
+        let (Some(a), Some(b)) = (positions.get(i), positions.get(j)) else {
+            return Err(StarryError::ComponentNotFound(type_name::<T>()));
+        };
+
+        self.components.swap(*a, *b);
+        self.rebuild_entity_index();
+        Ok(self)
+    }
+
+    /// Same as `try_swap_components` but unwraps the value
+    pub fn swap_components<T: Component + 'static>(&mut self, i: usize, j: usize) -> &mut Self {
+        self.try_swap_components::<T>(i, j).unwrap()
+    }
+
+    /// Removes every component of type `T` and returns them
+    ///
+    /// # Errors
+    /// Will return a `StarryError::StillShared` if a component is shared with a cloned world, nothing is removed in that case
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Asset { id: u32 }
+    /// impl Component for Asset {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Asset { id: 1 }).add_component(Asset { id: 2 });
+    ///
+    /// let assets = world.try_drain_components::<Asset>().unwrap();
+    /// assert_eq!(assets.iter().map(|a| a.id).collect::<Vec<_>>(), vec![1, 2]);
+    /// assert!(world.try_get_components::<Asset>().is_err());
+    /// ```
+    pub fn try_drain_components<T: Component + 'static>(&mut self) -> Result<Vec<T>, StarryError> {
+        let id = TypeId::of::<T>();
+
+        if self.components.iter().any(|(v, t, _)| t == &id && Arc::strong_count(v) > 1) {
+            return Err(StarryError::StillShared(type_name::<T>()));
+        }
+
+        Ok(self.take_components(|t, _| t == id).into_iter().map(|(v, _, _)| {
+            let component = unsafe { component::downcast_locked::<T>(v) };
+            match Arc::try_unwrap(component) {
+                Ok(lock) => lock.into_inner(),
+                Err(_) => unreachable!("component was checked to be unshared")
+            }
+        }).collect())
+    }
+
+    /// Same as `try_drain_components` but unwraps the value
+    pub fn drain_components<T: Component + 'static>(&mut self) -> Vec<T> {
+        self.try_drain_components::<T>().unwrap()
+    }
+
+    /// Gets components based on a given type `T` and returns a Read guard
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if components are not found
+    /// # Example
+    /// ```rs
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestComponent { x: i32 }
+    /// impl Component for TestComponent {}
+    ///
+    /// fn test_system(world: &World) {
+    ///     let _resource = world.try_get_components::<TestResource>().unwrap();
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, test_system).add_component(TestResource { x: 0 }).add_component(TestResource { x: 1 });
+    /// ```
+    pub fn try_get_components<T: Component + 'static>(&self) -> Result<Vec<ComponentReadGuard<'_, T>>, StarryError> {
+        let comps = self.iter_components::<T>().collect::<Vec<MappedRwLockReadGuard<'_, T>>>();
+
+        if comps.is_empty() {
+            return Err(StarryError::ComponentNotFound(type_name::<T>()));
+        }
+
+        Ok(comps)
+    }
+
+    /// Same as `try_get_components` but unwraps the value
+    pub fn get_components<T: Component + 'static>(&self) -> Vec<ComponentReadGuard<'_, T>> {
+        self.try_get_components().unwrap()
+    }
+    
+    /// Gets components based on a given type `T` and returns a Write guard
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if components are not found
+    /// # Example
+    /// ```rs
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestComponent { x: i32 }
+    /// impl Component for TestComponent {}
+    ///
+    /// fn test_system(world: &World) {
+    ///     let _resource = world.try_get_components_mut::<TestResource>().unwrap();
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, test_system).add_component(TestResource { x: 0 }).add_component(TestResource { x: 1 });
+    /// ```
+    pub fn try_get_components_mut<T: Component + 'static>(&self) -> Result<Vec<ComponentWriteGuard<'_, T>>, StarryError> {
+        let id = TypeId::of::<T>();
+
+        let comps = self
+            .components
+            .iter()
+            .filter(|(_, t, _)| t == &id)
+            .map(|(v, _, _)| RwLockWriteGuard::map(v.write(), |r| {
+                unsafe { &mut *(r as *mut dyn Component as *mut T) }
+            }))
+            .collect::<Vec<MappedRwLockWriteGuard<'_, T>>>();
+
+        if comps.is_empty() {
+            return Err(StarryError::ComponentNotFound(type_name::<T>()));
+        }
+
+        Ok(comps)
+    }
+
+    /// Same as `try_get_components_mut` but unwraps the value
+    pub fn get_components_mut<T: Component + 'static>(&self) -> Vec<ComponentWriteGuard<'_, T>> {
+        self.try_get_components_mut().unwrap()
+    }
+
+    /// Gets the component of type `T` at a position among the components of type `T`,
+    /// so `component_by_index::<T>(2)` is the third component of type `T` in the order of `get_components`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if there are `index` or fewer components of type `T`
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Waypoint { x: i32 }
+    /// impl Component for Waypoint {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Marker;
+    /// impl Component for Marker {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Waypoint { x: 0 }).add_component(Marker).add_component(Waypoint { x: 5 });
+    ///
+    /// assert_eq!(world.component_by_index::<Waypoint>(1).unwrap().x, 5);
+    /// assert!(world.component_by_index::<Waypoint>(2).is_err());
+    /// ```
+    pub fn component_by_index<T: Component + 'static>(&self, index: usize) -> Result<ComponentReadGuard<'_, T>, StarryError> {
+        let component = self.nth_component::<T>(index).ok_or(StarryError::ComponentNotFound(type_name::<T>()))?;
+
+        Ok(RwLockReadGuard::map(component.read(), |r| {
+            unsafe { &*(r as *const dyn Component as *const T) }
+        }))
+    }
+
+    /// Lists the positions among the components of type `T` of every component that `predicate` returns `true` for,
+    /// for use with `component_by_index` and `modify_component`. Only read locks are taken, one component at a time.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Health { points: i32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Health { points: 0 }).add_component(Health { points: 50 }).add_component(Health { points: -5 });
+    ///
+    /// let dead = world.filter_components(|health: &Health| health.points <= 0);
+    /// assert_eq!(dead, vec![0, 2]);
+    /// ```
+    pub fn filter_components<T: Component + 'static>(&self, predicate: impl Fn(&T) -> bool) -> Vec<usize> {
+        self.iter_components::<T>().enumerate().filter(|(_, component)| predicate(component)).map(|(i, _)| i).collect()
+    }
+
+    /// The lock of the component at a position among the components of type `T`
+    fn nth_component<T: Component + 'static>(&self, index: usize) -> Option<&Arc<RwLock<dyn Component>>> {
+        let id = TypeId::of::<T>();
+        self.components.iter().filter(|(_, t, _)| t == &id).nth(index).map(|(component, _, _)| component)
+    }
+
+    /// Calls `f` with the component at a position among the components of type `T`, like `component_by_index`,
+    /// and releases the lock once `f` returns. Returns `None` if there are `index` or fewer components of type `T`.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Enemy { health: i32 }
+    /// impl Component for Enemy {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Enemy { health: 10 }).add_component(Enemy { health: 25 });
+    ///
+    /// assert_eq!(world.inspect_component(1, |enemy: &Enemy| enemy.health), Some(25));
+    /// assert_eq!(world.inspect_component(2, |enemy: &Enemy| enemy.health), None);
+    /// ```
+    pub fn inspect_component<T: Component + 'static, R>(&self, index: usize, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let component = self.nth_component::<T>(index)?.read();
+        Some(f(unsafe { &*(&*component as *const dyn Component as *const T) }))
+    }
+
+    /// Read locks the component at a position among the components of type `T` and returns a raw pointer to it,
+    /// for handing component data to foreign code. The lock is held until the pointer is passed to `release_component_ptr`,
+    /// so anything that write locks the component, including systems, blocks until then.
+    /// Returns `None` if there are `index` or fewer components of type `T`.
+    ///
+    /// While the lock is held the component can't be removed: `despawn`, `despawn_all`, `retain_components`,
+    /// `apply_diff` and the other removal paths leave it in the world, since removing it would need a write lock.
+    /// The pointer must not be dereferenced after it's released. Cloned and forked worlds don't share these locks.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// #[repr(C)]
+    /// struct Transform { x: f32, y: f32 }
+    /// impl Component for Transform {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Transform { x: 1.0, y: 2.0 });
+    ///
+    /// let transform = world.component_ptr::<Transform>(0).unwrap();
+    /// assert_eq!(unsafe { (*transform).y }, 2.0);
+    ///
+    /// assert!(world.release_component_ptr(transform));
+    /// assert!(!world.release_component_ptr(transform));
+    /// ```
+    pub fn component_ptr<T: Component + 'static>(&self, index: usize) -> Option<*const T> {
+        let guard = self.nth_component::<T>(index)?.read_arc();
+        let ptr = &*guard as *const dyn Component as *const T;
+        self.held_components.lock().entry(ptr as usize).or_default().push(guard);
+        Some(ptr)
+    }
+
+    /// Releases a read lock taken by `component_ptr`, returns `false` if the pointer isn't holding a lock.
+    /// A component locked by several calls to `component_ptr` stays locked until every lock is released.
+    pub fn release_component_ptr<T: Component + 'static>(&self, ptr: *const T) -> bool {
+        let mut held = self.held_components.lock();
+        let Some(guards) = held.get_mut(&(ptr as usize)) else {
+            return false;
+        };
+
+        guards.pop();
+        if guards.is_empty() {
+            held.remove(&(ptr as usize));
+        }
+        true
+    }
+
+    /// Same as `inspect_component` but `f` gets mutable access to the component.
+    /// Returns `false` if there are `index` or fewer components of type `T`.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Position { x: 0.0 });
+    ///
+    /// assert!(world.modify_component(0, |position: &mut Position| position.x += 1.0));
+    /// assert_eq!(world.get_components::<Position>()[0].x, 1.0);
+    /// ```
+    pub fn modify_component<T: Component + 'static>(&self, index: usize, f: impl FnOnce(&mut T)) -> bool {
+        let Some(component) = self.nth_component::<T>(index) else {
+            return false;
+        };
+
+        let mut component = component.write();
+        f(unsafe { &mut *(&mut *component as *mut dyn Component as *mut T) });
+        true
+    }
+
+    /// Lists the indices of every component of type `T` in the component storage, for use with `try_get_components_many`.
+    /// Indices are only valid until components are next added or removed.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Player { name: &'static str }
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Enemy;
+    /// impl Component for Enemy {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Enemy).add_component(Player { name: "hero" });
+    ///
+    /// let indices = world.component_index_of::<Player>();
+    /// assert_eq!(indices, vec![1]);
+    /// assert_eq!(world.get_components_many::<Player>(&indices)[0].name, "hero");
+    /// ```
+    pub fn component_index_of<T: Component + 'static>(&self) -> Vec<usize> {
+        let id = TypeId::of::<T>();
+        self.components.iter().enumerate().filter(|(_, (_, t, _))| t == &id).map(|(i, _)| i).collect()
+    }
+
+    /// Checks that every index points at a component of type `T` and that no index is repeated
+    fn check_component_indices<T: Component + 'static>(&self, indices: &[usize]) -> Result<(), StarryError> {
+        for (i, index) in indices.iter().enumerate() {
+            if indices[..i].contains(index) {
+                return Err(StarryError::DuplicateIndex(*index));
+            }
+            if self.components.get(*index).is_none_or(|(_, t, _)| t != &TypeId::of::<T>()) {
+                return Err(StarryError::ComponentNotFound(type_name::<T>()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the components of type `T` at certain indices of the component storage, in the same order as `indices`.
+    /// Indices follow the order of `iter_raw_components`.
+    ///
+    /// # Errors
+    /// Will return a `StarryError::DuplicateIndex` if an index is repeated
+    /// and a `StarryError::ComponentNotFound` if an index is out of range or points at a component of another type,
+    /// no locks are taken in either case
+    /// # Example
+    /// ```
+    /// use starry_ecs::{StarryError, World};
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestComponent { x: i32 }
+    /// impl Component for TestComponent {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(TestComponent { x: 0 }).add_component(TestComponent { x: 1 }).add_component(TestComponent { x: 2 });
+    ///
+    /// let components = world.try_get_components_many::<TestComponent>(&[2, 0]).unwrap();
+    /// assert_eq!(components.iter().map(|c| c.x).collect::<Vec<_>>(), vec![2, 0]);
+    /// assert!(matches!(world.try_get_components_many_mut::<TestComponent>(&[1, 1]), Err(StarryError::DuplicateIndex(1))));
+    /// ```
+    pub fn try_get_components_many<T: Component + 'static>(&self, indices: &[usize]) -> Result<Vec<ComponentReadGuard<'_, T>>, StarryError> {
+        self.check_component_indices::<T>(indices)?;
+
+        Ok(indices.iter().map(|index| RwLockReadGuard::map(self.components[*index].0.read(), |r| {
+            unsafe { &*(r as *const dyn Component as *const T) }
+        })).collect())
+    }
+
+    /// Same as `try_get_components_many` but unwraps the value
+    pub fn get_components_many<T: Component + 'static>(&self, indices: &[usize]) -> Vec<ComponentReadGuard<'_, T>> {
+        self.try_get_components_many(indices).unwrap()
+    }
+
+    /// Same as `try_get_components_many` but returns Write guards
+    ///
+    /// # Errors
+    /// Will return a `StarryError::DuplicateIndex` if an index is repeated
+    /// and a `StarryError::ComponentNotFound` if an index is out of range or points at a component of another type,
+    /// no locks are taken in either case
+    pub fn try_get_components_many_mut<T: Component + 'static>(&self, indices: &[usize]) -> Result<Vec<ComponentWriteGuard<'_, T>>, StarryError> {
+        self.check_component_indices::<T>(indices)?;
+
+        Ok(indices.iter().map(|index| RwLockWriteGuard::map(self.components[*index].0.write(), |r| {
+            unsafe { &mut *(r as *mut dyn Component as *mut T) }
+        })).collect())
+    }
+
+    /// Same as `try_get_components_many_mut` but unwraps the value
+    pub fn get_components_many_mut<T: Component + 'static>(&self, indices: &[usize]) -> Vec<ComponentWriteGuard<'_, T>> {
+        self.try_get_components_many_mut(indices).unwrap()
+    }
+
+    /// Same as `try_get_components` but gives up if every lock isn't acquired within `timeout`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if components are not found
+    /// and a `StarryError::LockTimeout` if a component stays write locked past the timeout
+    pub fn try_get_components_timeout<T: Component + 'static>(&self, timeout: Duration) -> Result<Vec<ComponentReadGuard<'_, T>>, StarryError> {
+        let id = TypeId::of::<T>();
+        let deadline = Instant::now() + timeout;
+
+        let comps = self
+            .components
+            .iter()
+            .filter(|(_, t, _)| t == &id)
+            .map(|(v, _, _)| {
+                let guard = v.try_read_until(deadline).ok_or(StarryError::LockTimeout(type_name::<T>()))?;
+                Ok(RwLockReadGuard::map(guard, |r| {
+                    unsafe { &*(r as *const dyn Component as *const T) }
+                }))
+            })
+            .collect::<Result<Vec<_>, StarryError>>()?;
+
+        if comps.is_empty() {
+            return Err(StarryError::ComponentNotFound(type_name::<T>()));
+        }
+
+        Ok(comps)
+    }
+
+    /// Same as `try_get_components_mut` but gives up if every lock isn't acquired within `timeout`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if components are not found
+    /// and a `StarryError::LockTimeout` if a component stays locked past the timeout
+    pub fn try_get_components_mut_timeout<T: Component + 'static>(&self, timeout: Duration) -> Result<Vec<ComponentWriteGuard<'_, T>>, StarryError> {
+        let id = TypeId::of::<T>();
+        let deadline = Instant::now() + timeout;
+
+        let comps = self
+            .components
+            .iter()
+            .filter(|(_, t, _)| t == &id)
+            .map(|(v, _, _)| {
+                let guard = v.try_write_until(deadline).ok_or(StarryError::LockTimeout(type_name::<T>()))?;
+                Ok(RwLockWriteGuard::map(guard, |r| {
+                    unsafe { &mut *(r as *mut dyn Component as *mut T) }
+                }))
+            })
+            .collect::<Result<Vec<_>, StarryError>>()?;
+
+        if comps.is_empty() {
+            return Err(StarryError::ComponentNotFound(type_name::<T>()));
+        }
+
+        Ok(comps)
+    }
+
+    /// Runs a single step of the systems
+    ///
+    /// # Errors
+    /// Will return a `StarryError::CyclicDependency` if systems in a priority group depend on each other
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// World::new().try_single_step().unwrap();
+    /// ```
+    pub fn try_single_step(&mut self) -> Result<&mut Self, StarryError> {
+        let plan = self.schedule.execution_plan()?;
+        Ok(self.step_with(plan, true, |world, system| system(world)))
+    }
+
+    /// Runs a single step of a schedule other than the active one
+    ///
+    /// # Errors
+    /// Will return a `StarryError::CyclicDependency` if systems in a priority group depend on each other
+    pub fn try_run_schedule(&mut self, schedule: &Schedule) -> Result<&mut Self, StarryError> {
+        let plan = schedule.execution_plan()?;
+        Ok(self.step_with(plan, true, |world, system| system(world)))
+    }
+
+    /// Same as `try_run_schedule` but unwraps the value
+    pub fn run_schedule(&mut self, schedule: &Schedule) -> &mut Self {
+        self.try_run_schedule(schedule).unwrap()
+    }
+
+    /// Replaces the active schedule and returns the old one so it can be used again later
+    pub fn set_schedule(&mut self, schedule: Schedule) -> Schedule {
+        std::mem::replace(&mut self.schedule, schedule)
+    }
+
+    /// Runs a single step of an execution plan, calling `dispatch` to run each system.
+    /// Systems in a batch run in parallel unless `parallel` is false.
+    fn step_with(&mut self, plan: ExecutionPlan, parallel: bool, dispatch: impl Fn(&World, SystemType) + Sync) -> &mut Self {
+        if let Ok(mut frame_count) = self.try_get_resource_mut::<FrameCount>() {
+            frame_count.total += 1;
+            frame_count.since_start += 1;
+        }
+        for transition in self.state_transitions.clone() {
+            transition(self);
+        }
+        if self.schedule.startup_ran && !self.schedule.after_startup_ran {
+            self.schedule.after_startup_ran = true;
+            let systems = self.schedule.after_startup_systems.clone();
+
+            #[cfg(feature = "tracing")]
+            let after_startup = tracing::debug_span!("after_startup").entered();
+            self.run_batch(&systems, parallel, |system| {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!(parent: after_startup.id(), "system", name = std::any::type_name_of_val(system), address = ?(*system as *const ())).entered();
+                dispatch(self, *system)
+            });
+        }
+
+        for (priority, batches, exclusive) in plan.groups.iter() {
+            #[cfg(feature = "tracing")]
+            let group = tracing::debug_span!("priority_group", priority = priority).entered();
+
+            let run = |system: &SystemType| {
+                // Spans on rayon threads don't inherit the group span, so the parent is set explicitly
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!(parent: group.id(), "system", name = std::any::type_name_of_val(system), address = ?(*system as *const ())).entered();
+                if plan.should_run(self, *priority, *system) {
+                    dispatch(self, *system)
+                }
+            };
+            for batch in batches {
+                self.run_batch(batch, parallel, run);
+            }
+
+            for system in exclusive.iter().copied() {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!("exclusive_system", name = std::any::type_name_of_val(&system), address = ?(system as *const ())).entered();
+                system(self);
+            }
+        }
+        for clear_changed in self.change_trackers.values() {
+            clear_changed(self);
+        }
+        self.frame += 1;
+        self
+    }
+
+    /// Same as `single_step` but runs every system on the calling thread, one after another.
+    /// Systems in a priority group run in the order they were added, after the systems they depend on.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn first(_: &World) {}
+    /// fn second(_: &World) {}
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, first).add_system(DefaultOrdering::Run, second).run_systems_sequentially();
+    /// ```
+    pub fn run_systems_sequentially(&mut self) -> &mut Self {
+        let plan = self.schedule.execution_plan().unwrap();
+        self.step_with(plan, false, |world, system| system(world))
+    }
+
+    /// Same as `try_single_step` but unwraps the value
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// World::new().single_step();
+    /// ```
+    pub fn single_step(&mut self) -> &mut Self {
+        self.try_single_step().unwrap()
+    }
+
+    /// Runs `n` steps with `single_step`, then calls `f` with the world
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Ticks(u64);
+    /// impl Resource for Ticks {}
+    ///
+    /// fn tick(world: &World) {
+    ///     world.get_resource_mut::<Ticks>().0 += 1;
+    /// }
+    ///
+    /// World::new()
+    ///     .add_resource(Ticks(0))
+    ///     .add_system(DefaultOrdering::Run, tick)
+    ///     .run_n_then(10, |world| assert_eq!(world.get_resource::<Ticks>().0, 10))
+    ///     .run_n_then(5, |world| assert_eq!(world.get_resource::<Ticks>().0, 15));
+    /// ```
+    pub fn run_n_then<F: FnOnce(&mut World)>(&mut self, n: u64, f: F) -> &mut Self {
+        for _ in 0..n {
+            self.single_step();
+        }
+        f(self);
+        self
+    }
+
+    /// Runs a single step and returns how long each parallel system took, in the order they finished.
+    /// Exclusive systems aren't timed.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn physics(_: &World) {}
+    ///
+    /// let times = World::new().add_system(DefaultOrdering::Run, physics).measure_system_times();
+    /// assert_eq!(times.len(), 1);
+    /// ```
+    pub fn measure_system_times(&mut self) -> Vec<(SystemType, Duration)> {
+        let times = parking_lot::Mutex::new(vec![]);
+        let plan = self.schedule.execution_plan().unwrap();
+        self.step_with(plan, true, |world, system| {
+            let start = Instant::now();
+            system(world);
+            times.lock().push((system, start.elapsed()));
+        });
+        times.into_inner()
+    }
+
+    /// Runs a single step and returns how long the whole step took
+    pub fn measure_frame_time(&mut self) -> Duration {
+        let start = Instant::now();
+        self.single_step();
+        start.elapsed()
+    }
+
+    /// Same as `single_step` but also gives back how long the step took
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use starry_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let step = world.single_step_timed();
+    ///
+    /// assert!(step.duration() < Duration::from_secs(1));
+    /// assert_eq!(step.frame(), 1);
+    /// ```
+    pub fn single_step_timed(&mut self) -> TimedStep<'_> {
+        let duration = self.measure_frame_time();
+        TimedStep { world: self, duration }
+    }
+
+    /// Returns how many steps the world has run
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// assert_eq!(World::new().single_step().frame(), 1);
+    /// ```
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Runs a single system right away, outside of the normal schedule
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// fn debug_dump(world: &World) {
+    ///     world.list_resources();
+    /// }
+    ///
+    /// World::new().run_system_once(debug_dump);
+    /// ```
+    pub fn run_system_once(&mut self, system: SystemType) -> &mut Self {
+        system(self);
+        self
+    }
+
+    /// Runs startup systems of the active schedule and adds the `FrameCount` resource.
+    /// Startup systems only ever run once per schedule, calling `start` again does nothing.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    ///
+    /// World::new().start();
+    /// ```
+    pub fn start(&mut self) -> &mut Self {
+        if self.schedule.startup_ran {
+            return self;
+        }
+        self.schedule.startup_ran = true;
+        self.add_resource(FrameCount::default());
+
+        #[cfg(feature = "tracing")]
+        let startup = tracing::debug_span!("startup").entered();
+
+        let systems = self.schedule.enabled_startup_systems();
+        self.in_thread_pool(|| systems.par_iter().for_each(|system| {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!(parent: startup.id(), "system", name = std::any::type_name_of_val(system), address = ?(*system as *const ())).entered();
+            system(self)
+        }));
+
+        for system in self.schedule.exclusive_starting_systems.clone() {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!(parent: startup.id(), "exclusive_system", name = std::any::type_name_of_val(&system), address = ?(system as *const ())).entered();
+            system(self);
+        }
+        self
+    }
+
+    /// Calls `run` with every system of a batch, in parallel unless `parallel` is false
+    fn run_batch(&self, batch: &[SystemType], parallel: bool, run: impl Fn(&SystemType) + Send + Sync) {
+        if parallel {
+            self.in_thread_pool(|| batch.par_iter().for_each(run));
+        } else {
+            batch.iter().for_each(run);
+        }
+    }
+
+    /// Runs `f` in the thread pool set with `with_thread_pool`, or rayon's global pool if there isn't one
+    fn in_thread_pool<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        match &self.thread_pool {
+            Some(pool) => pool.install(f),
+            None => f()
+        }
+    }
+
+    /// Same as `single_step` but a panicking system calls `handler` with the system and the panic payload
+    /// instead of bringing down the whole step. Exclusive systems aren't covered.
+    ///
+    /// ```
+    /// use std::any::Any;
+    /// use starry_ecs::{SystemType, World};
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn broken(_: &World) {
+    ///     panic!("Broken system");
+    /// }
+    ///
+    /// fn log_panic(_: SystemType, payload: Box<dyn Any + Send>) {
+    ///     println!("System panicked: {:?}", payload.downcast_ref::<&str>());
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, broken).single_step_with_error_handler(log_panic);
+    /// ```
+    pub fn single_step_with_error_handler(&mut self, handler: ErrorHandler) -> &mut Self {
+        self.step_catching_panics(handler)
+    }
+
+    /// Runs a single step, calling `on_panic` with the system and the panic payload when a system panics
+    fn step_catching_panics(&mut self, on_panic: impl Fn(SystemType, Box<dyn Any + Send>) + Sync) -> &mut Self {
+        let plan = self.schedule.execution_plan().unwrap();
+        self.step_with(plan, true, |world, system| {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| system(world))) {
+                on_panic(system, payload);
+            }
+        })
+    }
+
+    /// Same as `run` but a panicking system calls `handler` instead of bringing down the application,
+    /// see `single_step_with_error_handler`
+    ///
+    /// ```no_run
+    /// use std::any::Any;
+    /// use starry_ecs::{SystemType, World};
+    ///
+    /// fn log_panic(_: SystemType, _: Box<dyn Any + Send>) {
+    ///     println!("A system panicked");
+    /// }
+    ///
+    /// World::new().run_with_error_handler(log_panic);
+    /// ```
+    pub fn run_with_error_handler(&mut self, handler: ErrorHandler) -> ! {
+        self.start();
+        loop {
+            self.single_step_with_error_handler(handler);
+        }
+    }
+
+    /// Sets the function that `single_step_recovering` and `run_recovering` call with every system panic
+    pub fn set_error_callback(&mut self, callback: fn(StarryError)) -> &mut Self {
+        self.error_callback = Some(callback);
+        self
+    }
+
+    /// Same as `single_step` but a panicking system is turned into a `StarryError::SystemPanic`
+    /// and passed to the callback from `set_error_callback`, or printed if there isn't one.
+    /// Exclusive systems aren't covered.
+    ///
+    /// ```
+    /// use starry_ecs::{StarryError, World};
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn bad_script(_: &World) {
+    ///     panic!("Unknown variable");
+    /// }
+    ///
+    /// fn report(err: StarryError) {
+    ///     assert_eq!(err.to_string(), "System panicked: `Unknown variable`");
+    /// }
+    ///
+    /// World::new().add_system(DefaultOrdering::Run, bad_script).set_error_callback(report).single_step_recovering();
+    /// ```
+    pub fn single_step_recovering(&mut self) -> &mut Self {
+        let callback = self.error_callback;
+        self.step_catching_panics(|_, payload| {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => payload.downcast_ref::<&str>().map_or("Unknown panic payload".to_string(), |message| message.to_string())
+            };
+            match callback {
+                Some(callback) => callback(StarryError::SystemPanic(message)),
+                None => eprintln!("{}", StarryError::SystemPanic(message))
+            }
+        })
+    }
+
+    /// Same as `run` but keeps running when a system panics, see `single_step_recovering`
+    ///
+    /// ```no_run
+    /// use starry_ecs::World;
+    ///
+    /// World::new().run_recovering();
+    /// ```
+    pub fn run_recovering(&mut self) -> ! {
+        self.start();
+        loop {
+            self.single_step_recovering();
+        }
+    }
+
+    /// Same as `run` but sends a `FrameEvent` through `sender` after every step,
+    /// and returns once the receiving end is dropped
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::thread;
+    /// use starry_ecs::{FrameEvent, World};
+    ///
+    /// let (sender, receiver) = mpsc::channel::<FrameEvent>();
+    /// let ui = thread::spawn(move || {
+    ///     for event in receiver.iter().take(3) {
+    ///         println!("Frame {} took {:?}", event.frame, event.duration);
+    ///     }
+    /// });
+    ///
+    /// World::new().run_with_channel(sender);
+    /// ui.join().unwrap();
+    /// ```
+    pub fn run_with_channel(&mut self, sender: mpsc::Sender<FrameEvent>) -> &mut Self {
+        self.start();
+        loop {
+            let duration = self.measure_frame_time();
+            if sender.send(FrameEvent { frame: self.frame, duration }).is_err() {
+                return self;
+            }
+        }
+    }
+
+    /// Builds a world with `f` on a new thread and runs it there, for worlds that have to live on a thread of their own
+    ///
+    /// # Errors
+    /// Will return an `io::Error` if the thread couldn't be spawned
+    /// # Panics
+    /// The thread panics if the world has non send resources that were added on another thread,
+    /// they have to be added inside `f`
+    /// # Example
+    /// ```no_run
+    /// use starry_ecs::World;
+    ///
+    /// let handle = World::run_on_thread(World::new).unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    pub fn run_on_thread<F: FnOnce() -> World + Send + 'static>(f: F) -> io::Result<JoinHandle<()>> {
+        thread::Builder::new().name("starry-world".to_string()).spawn(move || {
+            let mut world = f();
+            assert!(world.non_send_resources.is_on_current_thread(), "World with non send resources can't be run on another thread");
+            world.run();
+        })
+    }
+
+    /// Builds a world with `f` and runs it, both on the calling thread.
+    /// Unlike `run_on_thread` no thread is spawned, so calling it from the main thread keeps resources
+    /// that have to be created on the main OS thread, like windows and graphics contexts, on that thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use starry_ecs::World;
+    ///
+    /// World::build_and_run(World::new);
+    /// ```
+    pub fn build_and_run<F: FnOnce() -> World>(f: F) -> ! {
+        f().run()
+    }
+
+    /// Runs systems, running startup systems first if `start` wasn't called
+    ///
+    /// ```no_run
+    /// use starry_ecs::World;
+    ///
+    /// World::new().run();
+    /// ```
+    pub fn run(&mut self) -> ! {
+        self.start();
+        loop {
+            self.single_step();
+        }
+    }
+}
+
+/// Sent by `World::run_with_channel` after every step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameEvent {
+    /// How many steps the world has run, including this one
+    pub frame: u64,
+    /// How long the step took
+    pub duration: Duration
+}
+
+/// A world that just ran a step along with how long the step took, returned by `World::single_step_timed`
+pub struct TimedStep<'a> {
+    world: &'a mut World,
+    duration: Duration
+}
+
+impl TimedStep<'_> {
+    /// How long the step took
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+impl Deref for TimedStep<'_> {
+    type Target = World;
+
+    fn deref(&self) -> &World {
+        self.world
+    }
+}
+
+impl DerefMut for TimedStep<'_> {
+    fn deref_mut(&mut self) -> &mut World {
+        self.world
+    }
+}
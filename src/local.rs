@@ -0,0 +1,77 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use parking_lot::lock_api::ArcMutexGuard;
+use parking_lot::{Mutex, RawMutex, RwLock};
+
+use crate::{SystemType, World};
+
+/// State for every system, keyed by the system and the type of the state.
+/// The state belongs to the world it was created in, so a cloned or forked world starts without any.
+#[derive(Default)]
+pub(crate) struct LocalStorage(RwLock<HashMap<(SystemType, TypeId), LocalState>>);
+
+/// The state of one system, locked while a `Local` for it is alive
+type LocalState = Arc<Mutex<Box<dyn Any + Send>>>;
+
+impl Clone for LocalStorage {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// State that belongs to a single system, returned by `World::get_local`.
+/// The state is locked for as long as the `Local` is alive.
+pub struct Local<T: 'static> {
+    guard: ArcMutexGuard<RawMutex, Box<dyn Any + Send>>,
+    state: std::marker::PhantomData<T>
+}
+
+impl<T: 'static> Deref for Local<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.downcast_ref().unwrap()
+    }
+}
+
+impl<T: 'static> DerefMut for Local<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.guard.downcast_mut().unwrap()
+    }
+}
+
+impl World {
+    /// Gets the state of type `T` that belongs to `system`, creating it with `T::default()` on first access.
+    /// Every system gets its own state, even when they use the same type.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// fn count_runs(world: &World) {
+    ///     *world.get_local::<u32>(count_runs) += 1;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_system(DefaultOrdering::Run, count_runs).single_step().single_step();
+    ///
+    /// assert_eq!(*world.get_local::<u32>(count_runs), 2);
+    /// ```
+    pub fn get_local<T: Default + Send + 'static>(&self, system: SystemType) -> Local<T> {
+        let key = (system, TypeId::of::<T>());
+
+        let existing = self.locals.0.read().get(&key).cloned();
+        let state = match existing {
+            Some(state) => state,
+            None => self.locals.0.write().entry(key).or_insert_with(|| Arc::new(Mutex::new(Box::new(T::default())))).clone()
+        };
+
+        Local {
+            guard: state.lock_arc(),
+            state: std::marker::PhantomData
+        }
+    }
+}
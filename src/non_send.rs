@@ -0,0 +1,139 @@
+use std::any::{TypeId, type_name};
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::thread::{self, ThreadId};
+
+use crate::{StarryError, World};
+
+/// Marker trait for resources that aren't `Send` or `Sync`, like platform handles that must stay on one thread
+pub trait NonSendResource: Debug {}
+
+/// Resources added with `World::add_non_send_resource` and the thread they can be accessed from.
+///
+/// The world is `Send` and `Sync`, so the storage makes sure the `Rc`s are only ever touched on that thread:
+/// cloning it anywhere else panics, and dropping it anywhere else leaks the resources and panics.
+pub(crate) struct NonSendStorage {
+    resources: HashMap<TypeId, Rc<RefCell<dyn NonSendResource>>>,
+    main_thread: ThreadId
+}
+
+impl Default for NonSendStorage {
+    fn default() -> Self {
+        Self {
+            resources: HashMap::new(),
+            main_thread: thread::current().id()
+        }
+    }
+}
+
+impl NonSendStorage {
+    /// Panics unless called from the thread the world was created on
+    fn assert_main_thread(&self, name: &'static str) {
+        assert!(
+            thread::current().id() == self.main_thread,
+            "Non send resource `{}` accessed outside of the main thread", name
+        );
+    }
+
+    /// Whether the resources can be used from the current thread, which is always the case if there are none
+    pub(crate) fn is_on_current_thread(&self) -> bool {
+        self.resources.is_empty() || thread::current().id() == self.main_thread
+    }
+}
+
+impl Clone for NonSendStorage {
+    fn clone(&self) -> Self {
+        assert!(self.is_on_current_thread(), "World with non send resources cloned outside of the main thread");
+        Self {
+            resources: self.resources.clone(),
+            main_thread: self.main_thread
+        }
+    }
+}
+
+impl Drop for NonSendStorage {
+    fn drop(&mut self) {
+        if self.is_on_current_thread() {
+            return;
+        }
+
+        // Dropping the `Rc`s here could race with the main thread, leaking them is the only safe option
+        std::mem::forget(std::mem::take(&mut self.resources));
+        if !thread::panicking() {
+            panic!("World with non send resources dropped outside of the main thread");
+        }
+    }
+}
+
+impl World {
+    /// Adds a resource that isn't `Send` or `Sync`.
+    /// It can only be accessed from the thread the world was created on, so parallel systems can't use it,
+    /// only exclusive systems and systems run with `run_systems_sequentially` on that thread can.
+    /// Once one is added, the world can only be cloned or dropped on that thread as well.
+    /// If an existing resource exists, it will not be replaced.
+    ///
+    /// # Panics
+    /// Panics if called outside of the thread the world was created on
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use starry_ecs::World;
+    /// use starry_ecs::non_send::NonSendResource;
+    ///
+    /// #[derive(Debug)]
+    /// struct GlContext { handle: Rc<u32> }
+    /// impl NonSendResource for GlContext {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_non_send_resource(GlContext { handle: Rc::new(1) });
+    ///
+    /// assert_eq!(*world.get_non_send_resource::<GlContext>().handle, 1);
+    /// ```
+    pub fn add_non_send_resource<T: NonSendResource + 'static>(&mut self, resource: T) -> &mut Self {
+        self.non_send_resources.assert_main_thread(type_name::<T>());
+        self.non_send_resources.resources.entry(TypeId::of::<T>()).or_insert(Rc::new(RefCell::new(resource)));
+        self
+    }
+
+    /// Gets a non send resource based on a given type `T` and returns a borrow of it
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// # Panics
+    /// Panics if called outside of the thread the world was created on, or if the resource is mutably borrowed
+    pub fn try_get_non_send_resource<T: NonSendResource + 'static>(&self) -> Result<Ref<'_, T>, StarryError> {
+        self.non_send_resources.assert_main_thread(type_name::<T>());
+        let resource = self.non_send_resources.resources.get(&TypeId::of::<T>()).ok_or(StarryError::ResourceNotFound(type_name::<T>()))?;
+
+        Ok(Ref::map(resource.borrow(), |r| {
+            unsafe { &*(r as *const dyn NonSendResource as *const T) }
+        }))
+    }
+
+    /// Same as `try_get_non_send_resource` but unwraps the value
+    pub fn get_non_send_resource<T: NonSendResource + 'static>(&self) -> Ref<'_, T> {
+        self.try_get_non_send_resource::<T>().unwrap()
+    }
+
+    /// Gets a non send resource based on a given type `T` and returns a mutable borrow of it
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceNotFound` if the resource is not found
+    /// # Panics
+    /// Panics if called outside of the thread the world was created on, or if the resource is already borrowed
+    pub fn try_get_non_send_resource_mut<T: NonSendResource + 'static>(&self) -> Result<RefMut<'_, T>, StarryError> {
+        self.non_send_resources.assert_main_thread(type_name::<T>());
+        let resource = self.non_send_resources.resources.get(&TypeId::of::<T>()).ok_or(StarryError::ResourceNotFound(type_name::<T>()))?;
+
+        Ok(RefMut::map(resource.borrow_mut(), |r| {
+            unsafe { &mut *(r as *mut dyn NonSendResource as *mut T) }
+        }))
+    }
+
+    /// Same as `try_get_non_send_resource_mut` but unwraps the value
+    pub fn get_non_send_resource_mut<T: NonSendResource + 'static>(&self) -> RefMut<'_, T> {
+        self.try_get_non_send_resource_mut::<T>().unwrap()
+    }
+}
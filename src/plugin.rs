@@ -0,0 +1,222 @@
+use std::any::TypeId;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::World;
+use crate::resources::FrameCount;
+
+/// A reusable piece of world configuration
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::plugin::Plugin;
+/// use starry_ecs::systems::DefaultOrdering;
+///
+/// fn greet(_: &World) {
+///     println!("Hello, world!");
+/// }
+///
+/// struct GreetPlugin;
+///
+/// impl Plugin for GreetPlugin {
+///     fn build(self, world: &mut World) {
+///         world.add_system(DefaultOrdering::Run, greet);
+///     }
+/// }
+///
+/// World::new().add_plugin(GreetPlugin).single_step();
+/// ```
+pub trait Plugin: BoxedPlugin {
+    /// Adds the plugin's systems, resources and components to the world
+    fn build(self, world: &mut World);
+}
+
+/// Lets boxed plugins be built, implemented for every `Plugin`
+#[doc(hidden)]
+pub trait BoxedPlugin {
+    /// Builds a boxed plugin
+    fn build_boxed(self: Box<Self>, world: &mut World);
+
+    /// The type of the plugin, so it can be removed from a `PluginGroupBuilder`
+    fn plugin_type_id(&self) -> TypeId;
+}
+
+impl<P: Plugin + 'static> BoxedPlugin for P {
+    fn build_boxed(self: Box<Self>, world: &mut World) {
+        (*self).build(world)
+    }
+
+    fn plugin_type_id(&self) -> TypeId {
+        TypeId::of::<P>()
+    }
+}
+
+/// A set of plugins published together, added to a world with `World::add_plugin_group`
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::plugin::{Plugin, PluginGroup};
+///
+/// struct InputPlugin;
+/// impl Plugin for InputPlugin {
+///     fn build(self, _: &mut World) {}
+/// }
+///
+/// struct AudioPlugin;
+/// impl Plugin for AudioPlugin {
+///     fn build(self, _: &mut World) {}
+/// }
+///
+/// struct EnginePlugins;
+/// impl PluginGroup for EnginePlugins {
+///     fn plugins(self) -> Vec<Box<dyn Plugin>> {
+///         vec![Box::new(InputPlugin), Box::new(AudioPlugin)]
+///     }
+/// }
+///
+/// World::new().add_plugin_group(EnginePlugins);
+/// ```
+pub trait PluginGroup {
+    /// The plugins of the group, in the order they're added to the world
+    fn plugins(self) -> Vec<Box<dyn Plugin>>;
+}
+
+/// A plugin group put together one plugin at a time, or taken from another group to leave some of its plugins out
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::plugin::{DefaultPlugins, Plugin, PluginGroupBuilder, TimePlugin};
+///
+/// struct FirstPlugin;
+/// impl Plugin for FirstPlugin {
+///     fn build(self, _: &mut World) {}
+/// }
+///
+/// struct SecondPlugin;
+/// impl Plugin for SecondPlugin {
+///     fn build(self, _: &mut World) {}
+/// }
+///
+/// World::new()
+///     .add_plugin_group(PluginGroupBuilder::new().with(FirstPlugin).with(SecondPlugin))
+///     .add_plugin_group(PluginGroupBuilder::from_group(DefaultPlugins).without::<TimePlugin>());
+/// ```
+#[derive(Default)]
+pub struct PluginGroupBuilder {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl PluginGroupBuilder {
+    /// Creates an empty plugin group
+    pub fn new() -> Self {
+        Self { plugins: vec![] }
+    }
+
+    /// Creates a builder with every plugin of another group
+    pub fn from_group<G: PluginGroup>(group: G) -> Self {
+        Self { plugins: group.plugins() }
+    }
+
+    /// Adds a plugin to the group
+    pub fn with<P: Plugin + 'static>(mut self, plugin: P) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    /// Removes every plugin of type `P` from the group
+    pub fn without<P: Plugin + 'static>(mut self) -> Self {
+        self.plugins.retain(|plugin| plugin.plugin_type_id() != TypeId::of::<P>());
+        self
+    }
+}
+
+impl PluginGroup for PluginGroupBuilder {
+    fn plugins(self) -> Vec<Box<dyn Plugin>> {
+        self.plugins
+    }
+}
+
+impl Plugin for PluginGroupBuilder {
+    fn build(self, world: &mut World) {
+        world.add_plugin_group(self);
+    }
+}
+
+/// Adds the `FrameCount` resource, which `World::start` would otherwise add
+pub struct FrameCountPlugin;
+
+impl Plugin for FrameCountPlugin {
+    fn build(self, world: &mut World) {
+        world.add_resource(FrameCount::default());
+    }
+}
+
+/// Adds delta time and timers, see `World::add_timer_support`
+pub struct TimePlugin;
+
+impl Plugin for TimePlugin {
+    fn build(self, world: &mut World) {
+        world.add_timer_support();
+    }
+}
+
+/// Adds the `Events<E>` resource for events of type `E`, see `World::add_event`
+pub struct EventPlugin<E> {
+    event: PhantomData<E>
+}
+
+impl<E> EventPlugin<E> {
+    /// Creates the plugin for events of type `E`
+    pub fn new() -> Self {
+        Self { event: PhantomData }
+    }
+}
+
+impl<E> Default for EventPlugin<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Clone + Debug + Send + Sync + 'static> Plugin for EventPlugin<E> {
+    fn build(self, world: &mut World) {
+        world.add_event::<E>();
+    }
+}
+
+/// The plugins most worlds want, `FrameCountPlugin` and `TimePlugin`.
+/// Events are typed, so an `EventPlugin` has to be added for each type of event.
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::plugin::DefaultPlugins;
+/// use starry_ecs::time::DeltaTime;
+///
+/// let mut world = World::new();
+/// world.add_plugin_group(DefaultPlugins).single_step();
+///
+/// assert!(world.try_get_resource::<DeltaTime>().is_ok());
+/// ```
+pub struct DefaultPlugins;
+
+impl PluginGroup for DefaultPlugins {
+    fn plugins(self) -> Vec<Box<dyn Plugin>> {
+        vec![Box::new(FrameCountPlugin), Box::new(TimePlugin)]
+    }
+}
+
+impl World {
+    /// Adds a plugin to the world
+    pub fn add_plugin<P: Plugin>(&mut self, plugin: P) -> &mut Self {
+        plugin.build(self);
+        self
+    }
+
+    /// Adds every plugin of a group to the world, in the order the group lists them
+    pub fn add_plugin_group<G: PluginGroup>(&mut self, group: G) -> &mut Self {
+        for plugin in group.plugins() {
+            plugin.build_boxed(self);
+        }
+        self
+    }
+}
@@ -0,0 +1,271 @@
+use std::any::{TypeId, type_name};
+use std::marker::PhantomData;
+
+use parking_lot::RwLockReadGuard;
+
+use crate::{ComponentReadGuard, ComponentWriteGuard, StarryError, World};
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::systems::SystemMeta;
+
+/// A single part of a `Query`, either `&T` or `&mut T` for a component type `T`, or `Entity` itself
+pub trait QueryParam {
+    /// What the parameter fetches for each entity
+    type Item<'w>;
+
+    /// Fetches the parameter for an entity, `None` if the entity doesn't have it
+    fn fetch(world: &World, entity: Entity) -> Option<Self::Item<'_>>;
+
+    /// Declares what the parameter reads and writes
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        meta
+    }
+}
+
+impl<T: Component + 'static> QueryParam for &T {
+    type Item<'w> = ComponentReadGuard<'w, T>;
+
+    fn fetch(world: &World, entity: Entity) -> Option<Self::Item<'_>> {
+        world.try_get_component_by_entity::<T>(entity).ok()
+    }
+
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        meta.reads::<T>()
+    }
+}
+
+impl<T: Component + 'static> QueryParam for &mut T {
+    type Item<'w> = ComponentWriteGuard<'w, T>;
+
+    fn fetch(world: &World, entity: Entity) -> Option<Self::Item<'_>> {
+        world.try_get_component_mut_by_entity::<T>(entity).ok()
+    }
+
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        meta.writes::<T>()
+    }
+}
+
+impl QueryParam for Entity {
+    type Item<'w> = Entity;
+
+    fn fetch(_: &World, entity: Entity) -> Option<Self::Item<'_>> {
+        Some(entity)
+    }
+}
+
+/// A set of components fetched together from the same entity by `World::query`
+pub trait Query {
+    /// What the query yields for each matching entity
+    type Item<'w>;
+
+    /// Fetches the query for an entity, `None` if the entity doesn't match
+    fn fetch(world: &World, entity: Entity) -> Option<Self::Item<'_>>;
+
+    /// Declares the components the query reads and writes
+    fn describe(meta: SystemMeta) -> SystemMeta;
+}
+
+impl<T: QueryParam> Query for T {
+    type Item<'w> = T::Item<'w>;
+
+    fn fetch(world: &World, entity: Entity) -> Option<Self::Item<'_>> {
+        T::fetch(world, entity)
+    }
+
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        T::describe(meta)
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($param:ident),+) => {
+        impl<$($param: QueryParam),+> Query for ($($param,)+) {
+            type Item<'w> = ($($param::Item<'w>,)+);
+
+            fn fetch(world: &World, entity: Entity) -> Option<Self::Item<'_>> {
+                Some(($($param::fetch(world, entity)?,)+))
+            }
+
+            fn describe(meta: SystemMeta) -> SystemMeta {
+                $(let meta = $param::describe(meta);)+
+                meta
+            }
+        }
+    };
+}
+
+impl_query_tuple!(A);
+impl_query_tuple!(A, B);
+impl_query_tuple!(A, B, C);
+impl_query_tuple!(A, B, C, D);
+
+/// A filter on which entities a query matches, without fetching any components
+pub trait QueryFilter {
+    /// Whether the entity passes the filter
+    fn matches(world: &World, entity: Entity) -> bool;
+}
+
+/// Only matches entities that have a component of type `T`
+pub struct With<T>(PhantomData<T>);
+
+impl<T: Component + 'static> QueryFilter for With<T> {
+    fn matches(world: &World, entity: Entity) -> bool {
+        world.has_component_type(entity, TypeId::of::<T>())
+    }
+}
+
+/// Only matches entities that don't have a component of type `T`
+pub struct Without<T>(PhantomData<T>);
+
+impl<T: Component + 'static> QueryFilter for Without<T> {
+    fn matches(world: &World, entity: Entity) -> bool {
+        !world.has_component_type(entity, TypeId::of::<T>())
+    }
+}
+
+impl QueryFilter for () {
+    fn matches(_: &World, _: Entity) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_query_filter_tuple {
+    ($($filter:ident),+) => {
+        impl<$($filter: QueryFilter),+> QueryFilter for ($($filter,)+) {
+            fn matches(world: &World, entity: Entity) -> bool {
+                $($filter::matches(world, entity))&&+
+            }
+        }
+    };
+}
+
+impl_query_filter_tuple!(A);
+impl_query_filter_tuple!(A, B);
+impl_query_filter_tuple!(A, B, C);
+impl_query_filter_tuple!(A, B, C, D);
+
+/// An iterator over the results of `World::query` and `World::query_filtered`
+pub struct QueryResult<'w, Q: Query, F: QueryFilter = ()> {
+    world: &'w World,
+    entities: std::vec::IntoIter<Entity>,
+    query: PhantomData<(Q, F)>
+}
+
+impl<'w, Q: Query, F: QueryFilter> Iterator for QueryResult<'w, Q, F> {
+    type Item = Q::Item<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let world = self.world;
+        self.entities.find_map(|entity| {
+            if F::matches(world, entity) {
+                Q::fetch(world, entity)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl World {
+    /// Queries every entity that has all the components in `Q`.
+    /// Components are locked one entity at a time as the result is iterated.
+    ///
+    /// # Panics
+    /// Panics if `Q` takes `&mut T` along with another `&T` or `&mut T` of the same component type, which would deadlock
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::entity::Entity;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Velocity { x: i32 }
+    /// impl Component for Velocity {}
+    ///
+    /// let mut world = World::new();
+    /// let moving = world.spawn().with(Position { x: 0 }).with(Velocity { x: 1 }).id();
+    /// world.spawn().with(Position { x: 5 });
+    ///
+    /// for (entity, mut position, velocity) in world.query::<(Entity, &mut Position, &Velocity)>() {
+    ///     assert_eq!(entity, moving);
+    ///     position.x += velocity.x;
+    /// }
+    /// assert_eq!(world.get_component_by_entity::<Position>(moving).x, 1);
+    /// ```
+    pub fn query<Q: Query>(&self) -> QueryResult<'_, Q> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// Queries every entity that has all the components in `Q` and passes the filter `F`, see `query`
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::query::{With, Without};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Velocity { x: i32 }
+    /// impl Component for Velocity {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Static;
+    /// impl Component for Static {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Position { x: 0 }).with(Velocity { x: 1 });
+    /// world.spawn().with(Position { x: 1 }).with(Velocity { x: 0 }).with(Static);
+    /// world.spawn().with(Position { x: 2 });
+    ///
+    /// let moving = world.query_filtered::<&Position, (With<Velocity>, Without<Static>)>().map(|p| p.x).collect::<Vec<_>>();
+    /// assert_eq!(moving, vec![0]);
+    /// ```
+    pub fn query_filtered<Q: Query, F: QueryFilter>(&self) -> QueryResult<'_, Q, F> {
+        self.check_exclusive_access(&Q::describe(SystemMeta::new(type_name::<Q>())));
+        QueryResult {
+            world: self,
+            entities: self.entities().into_iter(),
+            query: PhantomData
+        }
+    }
+
+    /// Gets every component of type `T` that belongs to an entity passing the filter `F`
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ComponentNotFound` if no components pass the filter
+    pub fn try_get_components_filtered<T: Component + 'static, F: QueryFilter>(&self) -> Result<Vec<ComponentReadGuard<'_, T>>, StarryError> {
+        let id = TypeId::of::<T>();
+
+        let comps = self.components
+            .iter()
+            .filter(|(_, t, e)| t == &id && e.is_some_and(|entity| F::matches(self, entity)))
+            .map(|(v, _, _)| RwLockReadGuard::map(v.read(), |r| {
+                unsafe { &*(r as *const dyn Component as *const T) }
+            }))
+            .collect::<Vec<_>>();
+
+        if comps.is_empty() {
+            return Err(StarryError::ComponentNotFound(type_name::<T>()));
+        }
+
+        Ok(comps)
+    }
+
+    /// Same as `try_get_components_filtered` but unwraps the value
+    pub fn get_components_filtered<T: Component + 'static, F: QueryFilter>(&self) -> Vec<ComponentReadGuard<'_, T>> {
+        self.try_get_components_filtered::<T, F>().unwrap()
+    }
+
+    /// Whether an entity has a component with the given type
+    pub(crate) fn has_component_type(&self, entity: Entity, id: TypeId) -> bool {
+        self.entity_index.contains_key(&(id, entity))
+    }
+}
@@ -1,4 +1,194 @@
 use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::Duration;
+
+use dyn_clone::{DynClone, clone_trait_object};
+use parking_lot::RwLock;
+
+use crate::{ResourceWriteGuard, World};
 
 /// Marker trait to say what's a Resource
-pub trait Resource: Debug {}
+pub trait Resource: DynClone + Debug {
+    /// Called by `World::tick_resources` so the resource can update itself, does nothing by default
+    fn tick(&mut self, _delta: Duration) {}
+}
+
+clone_trait_object!(Resource);
+
+/// Derives `Resource` along with `Clone` and `Debug`
+#[cfg(feature = "derive")]
+pub use starry_ecs_derive::Resource;
+
+/// Lets a resource be shared under the type of another resource with `World::add_resource_alias`
+///
+/// # Safety
+/// `Self` must have the same layout as `T`, for example by being a `#[repr(transparent)]` wrapper around it
+pub unsafe trait ResourceAlias<T: Resource>: Resource {}
+
+/// How many steps the world has run, kept up to date by the world once added.
+/// `run` and `start` add it, or add it yourself to count steps taken with `single_step`.
+/// Both counts go up at the beginning of every step, before any system runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameCount {
+    /// Steps run since the resource was added
+    pub total: u64,
+    /// Steps run since the resource was added or `World::clear_systems` was last called
+    pub since_start: u64
+}
+impl Resource for FrameCount {}
+
+/// A resource that remembers whether it was mutably accessed during the current step, added with `World::add_resource_tracked`.
+/// Any mutable dereference counts as a change, even if nothing is written.
+#[derive(Clone, Debug)]
+pub struct TrackedResource<T: Resource + Clone> {
+    value: T,
+    changed: bool
+}
+impl<T: Resource + Clone + 'static> Resource for TrackedResource<T> {
+    fn tick(&mut self, delta: Duration) {
+        self.value.tick(delta);
+    }
+}
+
+impl<T: Resource + Clone> TrackedResource<T> {
+    /// Wraps a resource, it starts out unchanged
+    pub fn new(value: T) -> Self {
+        Self { value, changed: false }
+    }
+
+    /// Whether the resource was mutably accessed since the end of the last step
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+}
+
+impl<T: Resource + Clone> Deref for TrackedResource<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Resource + Clone> DerefMut for TrackedResource<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.changed = true;
+        &mut self.value
+    }
+}
+
+/// Marks the tracked resource of type `T` as unchanged, called by the world at the end of every step
+pub(crate) fn clear_changed<T: Resource + Clone + 'static>(world: &World) {
+    if let Ok(mut resource) = world.try_get_resource_mut::<TrackedResource<T>>() {
+        resource.changed = false;
+    }
+}
+
+/// A resource that calls subscribers with its new value after it's changed, added with `World::add_reactive_resource`.
+/// Subscribers are called when a guard from `World::get_reactive_resource_mut` that mutably accessed the resource is dropped,
+/// changes made through `World::get_resource_mut` don't notify anyone.
+#[derive(Clone, Debug)]
+pub struct ReactiveResource<T: Resource + Clone> {
+    value: T,
+    subscribers: Vec<fn(&T)>
+}
+impl<T: Resource + Clone + 'static> Resource for ReactiveResource<T> {
+    fn tick(&mut self, delta: Duration) {
+        self.value.tick(delta);
+    }
+}
+
+impl<T: Resource + Clone> ReactiveResource<T> {
+    /// Wraps a resource with no subscribers
+    pub fn new(value: T) -> Self {
+        Self { value, subscribers: vec![] }
+    }
+
+    /// Adds a function to call with the new value after every change
+    pub fn subscribe(&mut self, callback: fn(&T)) {
+        self.subscribers.push(callback);
+    }
+}
+
+impl<T: Resource + Clone> Deref for ReactiveResource<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Resource + Clone> DerefMut for ReactiveResource<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// Write guard returned by `World::get_reactive_resource_mut`, notifies the subscribers of the resource
+/// when it's dropped if it was mutably dereferenced
+pub struct ReactiveWriteGuard<'a, T: Resource + Clone> {
+    guard: ResourceWriteGuard<'a, ReactiveResource<T>>,
+    changed: bool
+}
+
+impl<'a, T: Resource + Clone> ReactiveWriteGuard<'a, T> {
+    pub(crate) fn new(guard: ResourceWriteGuard<'a, ReactiveResource<T>>) -> Self {
+        Self { guard, changed: false }
+    }
+}
+
+impl<T: Resource + Clone> Deref for ReactiveWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard.value
+    }
+}
+
+impl<T: Resource + Clone> DerefMut for ReactiveWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.changed = true;
+        &mut self.guard.value
+    }
+}
+
+impl<T: Resource + Clone> Drop for ReactiveWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.changed {
+            for subscriber in self.guard.subscribers.iter() {
+                subscriber(&self.guard.value);
+            }
+        }
+    }
+}
+
+/// Creates a resource and adds it to the world, registered with `World::add_resource_initializer`
+pub(crate) type ResourceInitializer = Arc<dyn Fn(&mut World) + Send + Sync>;
+
+/// Function that clones a type erased resource into a new lock
+pub(crate) type ResourceCloner = fn(&dyn Resource) -> Arc<RwLock<dyn Resource>>;
+
+/// Clones a resource of type `T` into a new lock
+pub(crate) fn clone_locked<T: Resource + 'static>(resource: &dyn Resource) -> Arc<RwLock<dyn Resource>> {
+    let resource = unsafe { &*(resource as *const dyn Resource as *const T) };
+    Arc::new(RwLock::new(dyn_clone::clone(resource)))
+}
+
+/// Function that compares two type erased resources of the same type
+pub(crate) type ResourceEq = fn(&dyn Resource, &dyn Resource) -> bool;
+
+/// Compares two resources of type `T` with `PartialEq`
+pub(crate) fn eq_erased<T: Resource + PartialEq + 'static>(a: &dyn Resource, b: &dyn Resource) -> bool {
+    let a = unsafe { &*(a as *const dyn Resource as *const T) };
+    let b = unsafe { &*(b as *const dyn Resource as *const T) };
+    a == b
+}
+
+/// Turns a type erased resource back into its concrete type
+///
+/// # Safety
+/// The resource must be of type `T`
+pub(crate) unsafe fn downcast_locked<T: Resource + 'static>(resource: Arc<RwLock<dyn Resource>>) -> Arc<RwLock<T>> {
+    Arc::from_raw(Arc::into_raw(resource) as *const RwLock<T>)
+}
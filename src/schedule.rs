@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::resources::FrameCount;
+use crate::systems::{self, SystemBatches, SystemMeta, SystemOrdering};
+use crate::{ExclusiveSystemType, StarryError, SystemType, World};
+
+/// Decides whether a system runs this step, checked right before the system would run
+pub(crate) type RunCondition = Arc<dyn Fn(&World) -> bool + Send + Sync>;
+
+/// How often a system added with `World::add_system_every_n_frames_with_offset` runs, as `(n, offset)`
+type FrameInterval = (u64, u64);
+
+/// Every priority group of a schedule with its parallel batches and exclusive systems, in the order they run
+pub(crate) struct ExecutionPlan {
+    pub(crate) groups: Vec<(i32, SystemBatches, Vec<ExclusiveSystemType>)>,
+    run_conditions: HashMap<SystemType, Vec<RunCondition>>,
+    /// For systems with frame intervals, how many occurrences at a priority have none and the intervals of the rest
+    frame_intervals: HashMap<(i32, SystemType), (usize, Vec<FrameInterval>)>,
+    /// How many occurrences of a system with frame intervals have asked to run this step
+    started: HashMap<(i32, SystemType), AtomicUsize>
+}
+
+impl ExecutionPlan {
+    /// Whether every run condition of `system` allows it to run, and whether this occurrence of it is due this step.
+    /// Occurrences of a system are interchangeable, so the first ones to ask run as long as
+    /// there are occurrences without a frame interval or with an interval that's due.
+    pub(crate) fn should_run(&self, world: &World, priority: i32, system: SystemType) -> bool {
+        if !self.run_conditions.get(&system).is_none_or(|conditions| conditions.iter().all(|condition| condition(world))) {
+            return false;
+        }
+        let Some((unconditional, intervals)) = self.frame_intervals.get(&(priority, system)) else {
+            return true;
+        };
+
+        let Ok(frame) = world.try_get_resource::<FrameCount>().map(|count| count.total.saturating_sub(1)) else {
+            return false;
+        };
+        let due = intervals.iter().filter(|(n, offset)| frame >= *offset && (frame - offset).is_multiple_of(*n)).count();
+        self.started[&(priority, system)].fetch_add(1, Ordering::Relaxed) < unconditional + due
+    }
+}
+
+/// Names a system that has no `SystemMeta` by its type and address, so systems of the same type can be told apart
+fn address_name<T>(system: &T, address: *const ()) -> String {
+    format!("{} at {:p}", std::any::type_name_of_val(system), address)
+}
+
+/// A set of systems that a world can run, separate from the world itself.
+///
+/// Every world has an active schedule that `add_system` and friends register into,
+/// `World::set_schedule` swaps it out and `World::run_schedule` runs any other schedule against the world.
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::schedule::Schedule;
+/// use starry_ecs::systems::DefaultOrdering;
+///
+/// fn game(_: &World) {}
+/// fn editor(_: &World) {}
+///
+/// let mut editor_schedule = Schedule::new();
+/// editor_schedule.add_system(DefaultOrdering::Run, editor);
+///
+/// let mut world = World::new();
+/// world.add_system(DefaultOrdering::Run, game);
+///
+/// let game_schedule = world.set_schedule(editor_schedule);
+/// world.single_step().run_schedule(&game_schedule);
+/// ```
+#[derive(Clone, Default)]
+pub struct Schedule {
+    pub(crate) systems: HashMap<i32, Vec<SystemType>>,
+    pub(crate) system_dependencies: HashMap<SystemType, Vec<SystemType>>,
+    pub(crate) exclusive_systems: HashMap<i32, Vec<ExclusiveSystemType>>,
+    pub(crate) starting_systems: Vec<SystemType>,
+    pub(crate) exclusive_starting_systems: Vec<ExclusiveSystemType>,
+    pub(crate) startup_ran: bool,
+    pub(crate) after_startup_systems: Vec<SystemType>,
+    pub(crate) after_startup_ran: bool,
+    pub(crate) system_meta: HashMap<SystemType, SystemMeta>,
+    pub(crate) run_conditions: HashMap<SystemType, Vec<RunCondition>>,
+    pub(crate) frame_intervals: HashMap<(i32, SystemType), Vec<FrameInterval>>,
+    pub(crate) startup_conditions: HashMap<SystemType, Vec<fn() -> bool>>,
+    pub(crate) system_labels: HashMap<&'static str, (i32, SystemType)>,
+}
+
+impl Schedule {
+    /// Creates an empty schedule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same as `World::add_system`
+    pub fn add_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: SystemType) -> &mut Self {
+        self.systems.entry(system_ordering.into()).or_default().push(system);
+
+        let dependencies = system_ordering.dependencies();
+        if !dependencies.is_empty() {
+            self.system_dependencies.entry(system).or_default().extend(dependencies);
+        }
+        self
+    }
+
+    /// Same as `World::add_system_at_priority`
+    pub fn add_system_at_priority(&mut self, priority: i32, system: SystemType) -> &mut Self {
+        self.systems.entry(priority).or_default().push(system);
+        self
+    }
+
+    /// Same as `World::add_system_after`
+    pub fn add_system_after<S: SystemOrdering + Copy>(&mut self, system_ordering: S, existing: SystemType, system: SystemType) -> &mut Self {
+        self.system_dependencies.entry(system).or_default().push(existing);
+        self.add_system(system_ordering, system)
+    }
+
+    /// Same as `World::add_system_before`
+    pub fn add_system_before<S: SystemOrdering + Copy>(&mut self, system_ordering: S, existing: SystemType, system: SystemType) -> &mut Self {
+        self.system_dependencies.entry(existing).or_default().push(system);
+        self.add_system(system_ordering, system)
+    }
+
+    /// Same as `World::add_system_group`
+    pub fn add_system_group(&mut self, priority: i32, systems: Vec<SystemType>) -> &mut Self {
+        self.systems.entry(priority).or_default().extend(systems);
+        self
+    }
+
+    /// Same as `World::add_system_group_sorted`
+    pub fn add_system_group_sorted(&mut self, priority: i32, systems: Vec<SystemType>) -> &mut Self {
+        for pair in systems.windows(2) {
+            self.system_dependencies.entry(pair[1]).or_default().push(pair[0]);
+        }
+        self.add_system_group(priority, systems)
+    }
+
+    /// Same as `World::add_system_labeled`
+    pub fn add_system_labeled<S: SystemOrdering + Copy>(&mut self, label: &'static str, system_ordering: S, system: SystemType) -> &mut Self {
+        self.system_labels.insert(label, (system_ordering.into(), system));
+        self.add_system(system_ordering, system)
+    }
+
+    /// Same as `World::remove_system_by_label`
+    pub fn remove_system_by_label(&mut self, label: &'static str) -> bool {
+        match self.system_labels.remove(label) {
+            Some((priority, system)) => self.remove_system_at(priority, system),
+            None => false
+        }
+    }
+
+    /// Same as `World::remove_system`
+    pub fn remove_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: SystemType) -> bool {
+        self.remove_system_at(system_ordering.into(), system)
+    }
+
+    /// Removes the first occurrence of a system from a priority group
+    fn remove_system_at(&mut self, priority: i32, system: SystemType) -> bool {
+        let Some(systems) = self.systems.get_mut(&priority) else {
+            return false;
+        };
+        let Some(position) = systems.iter().position(|s| std::ptr::fn_addr_eq(*s, system)) else {
+            return false;
+        };
+
+        systems.remove(position);
+        if systems.is_empty() {
+            self.systems.remove(&priority);
+        }
+
+        let left_at_priority = self.systems.get(&priority).map_or(0, |systems| systems.iter().filter(|s| std::ptr::fn_addr_eq(**s, system)).count());
+        if let Some(intervals) = self.frame_intervals.get_mut(&(priority, system)) {
+            // Occurrences without an interval are removed first, then the most recently added intervals
+            intervals.truncate(left_at_priority);
+            if intervals.is_empty() {
+                self.frame_intervals.remove(&(priority, system));
+            }
+        }
+        if left_at_priority == 0 {
+            self.system_labels.retain(|_, (p, s)| *p != priority || !std::ptr::fn_addr_eq(*s, system));
+        }
+        if !self.has_system(system) {
+            self.forget_system(system);
+        }
+        true
+    }
+
+    /// Drops the dependencies, meta and run conditions of a system that isn't in the schedule anymore
+    fn forget_system(&mut self, system: SystemType) {
+        self.system_dependencies.remove(&system);
+        self.system_meta.remove(&system);
+        self.run_conditions.remove(&system);
+    }
+
+    /// Same as `World::add_exclusive_system`
+    pub fn add_exclusive_system<S: SystemOrdering + Copy>(&mut self, system_ordering: S, system: ExclusiveSystemType) -> &mut Self {
+        self.exclusive_systems.entry(system_ordering.into()).or_default().push(system);
+        self
+    }
+
+    /// Same as `World::add_startup_system`
+    pub fn add_startup_system(&mut self, system: SystemType) -> &mut Self {
+        self.starting_systems.push(system);
+        self
+    }
+
+    /// Adds an occurrence of `system` that only runs every `n` steps counted by `FrameCount`, starting with step `offset`
+    pub(crate) fn add_system_every_n_frames<S: SystemOrdering + Copy>(&mut self, system_ordering: S, n: u64, offset: u64, system: SystemType) -> &mut Self {
+        self.frame_intervals.entry((system_ordering.into(), system)).or_default().push((n, offset));
+        self.add_system(system_ordering, system)
+    }
+
+    /// Only lets `system` run in steps where `condition` returns `true`
+    pub(crate) fn add_run_condition(&mut self, system: SystemType, condition: RunCondition) -> &mut Self {
+        self.run_conditions.entry(system).or_default().push(condition);
+        self
+    }
+
+    /// Same as `World::describe_system`
+    pub fn describe_system(&mut self, system: SystemType, meta: SystemMeta) -> &mut Self {
+        self.system_meta.insert(system, meta);
+        self
+    }
+
+    /// Same as `World::validate_no_conflicts`
+    pub fn validate_no_conflicts(&self) -> Vec<String> {
+        let plan = match self.execution_plan() {
+            Ok(plan) => plan,
+            Err(err) => return vec![err.to_string()]
+        };
+
+        let mut conflicts = vec![];
+        for batch in plan.groups.iter().flat_map(|(_, batches, _)| batches) {
+            let described = batch.iter().filter_map(|system| self.system_meta.get(system)).collect::<Vec<_>>();
+            for (i, meta) in described.iter().enumerate() {
+                for other in described[i + 1..].iter() {
+                    conflicts.extend(meta.conflicts_with(other));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Same as `World::add_exclusive_startup_system`
+    pub fn add_exclusive_startup_system(&mut self, system: ExclusiveSystemType) -> &mut Self {
+        self.exclusive_starting_systems.push(system);
+        self
+    }
+
+    /// Same as `World::add_system_after_startup`
+    pub fn add_system_after_startup(&mut self, system: SystemType) -> &mut Self {
+        self.after_startup_systems.push(system);
+        self
+    }
+
+    /// Same as `World::add_startup_system_with_condition`
+    pub fn add_startup_system_with_condition(&mut self, system: SystemType, condition: fn() -> bool) -> &mut Self {
+        self.startup_conditions.entry(system).or_default().push(condition);
+        self.add_startup_system(system)
+    }
+
+    /// Same as `World::remove_startup_system`
+    pub fn remove_startup_system(&mut self, system: SystemType) -> bool {
+        let Some(position) = self.starting_systems.iter().position(|s| std::ptr::fn_addr_eq(*s, system)) else {
+            return false;
+        };
+
+        self.starting_systems.remove(position);
+        if !self.starting_systems.iter().any(|s| std::ptr::fn_addr_eq(*s, system)) {
+            self.startup_conditions.remove(&system);
+        }
+        true
+    }
+
+    /// Startup systems whose conditions all pass, in the order they were added
+    pub(crate) fn enabled_startup_systems(&self) -> Vec<SystemType> {
+        self.starting_systems.iter().copied().filter(|system| {
+            self.startup_conditions.get(system).is_none_or(|conditions| conditions.iter().all(|condition| condition()))
+        }).collect()
+    }
+
+    /// A name for a system, taken from its `SystemMeta` if it was described
+    pub(crate) fn system_name(&self, system: SystemType) -> String {
+        match self.system_meta.get(&system) {
+            Some(meta) => meta.name().to_string(),
+            None => address_name(&system, system as *const ())
+        }
+    }
+
+    /// A name for an exclusive system, exclusive systems can't be described so it's always the type and address
+    pub(crate) fn exclusive_system_name(&self, system: ExclusiveSystemType) -> String {
+        address_name(&system, system as *const ())
+    }
+
+    /// Same as `World::system_execution_order`
+    pub fn system_execution_order(&self) -> String {
+        let plan = match self.execution_plan() {
+            Ok(plan) => plan,
+            Err(err) => return format!("{}\n", err)
+        };
+
+        let mut order = String::new();
+        for (priority, batches, exclusive) in plan.groups.iter() {
+            order.push_str(&format!("Priority group {}\n", priority));
+            for batch in batches {
+                let names = batch.iter().map(|system| self.system_name(*system)).collect::<Vec<_>>();
+                order.push_str(&format!("    runs [{}] in parallel\n", names.join(", ")));
+            }
+            for system in exclusive {
+                order.push_str(&format!("    then runs {} exclusively\n", self.exclusive_system_name(*system)));
+            }
+        }
+        order
+    }
+
+    /// Same as `World::clear_systems`
+    pub fn clear_systems(&mut self) -> &mut Self {
+        self.systems.clear();
+        self.system_dependencies.clear();
+        self.exclusive_systems.clear();
+        self.system_meta.clear();
+        self.run_conditions.clear();
+        self.frame_intervals.clear();
+        self.system_labels.clear();
+        self
+    }
+
+    /// Same as `World::clear_startup_systems`
+    pub fn clear_startup_systems(&mut self) -> &mut Self {
+        self.starting_systems.clear();
+        self.startup_conditions.clear();
+        self.exclusive_starting_systems.clear();
+        self.after_startup_systems.clear();
+        self
+    }
+
+    /// Same as `World::system_count`
+    pub fn system_count(&self) -> usize {
+        self.systems.values().map(Vec::len).sum()
+    }
+
+    /// Same as `World::system_count_at`
+    pub fn system_count_at<S: SystemOrdering + Copy>(&self, system_ordering: S) -> usize {
+        self.systems.get(&system_ordering.into()).map_or(0, Vec::len)
+    }
+
+    /// Same as `World::system_priorities`
+    pub fn system_priorities(&self) -> Vec<i32> {
+        let mut priorities = self.systems.keys().chain(self.exclusive_systems.keys()).copied().collect::<Vec<_>>();
+        priorities.sort();
+        priorities.dedup();
+        priorities
+    }
+
+    /// Same as `World::system_count_at_priority`
+    pub fn system_count_at_priority(&self, priority: i32) -> usize {
+        self.systems.get(&priority).map_or(0, Vec::len)
+    }
+
+    /// Same as `World::startup_system_count`
+    pub fn startup_system_count(&self) -> usize {
+        self.starting_systems.len()
+    }
+
+    /// Same as `World::has_system`
+    pub fn has_system(&self, system: SystemType) -> bool {
+        self.systems.values().flatten().any(|s| std::ptr::fn_addr_eq(*s, system))
+    }
+
+    /// Same as `World::has_startup_system`
+    pub fn has_startup_system(&self, system: SystemType) -> bool {
+        self.starting_systems.iter().any(|s| std::ptr::fn_addr_eq(*s, system))
+    }
+
+    /// Builds the execution plan for the systems, a list of parallel batches for every priority group
+    ///
+    /// # Errors
+    /// Will return a `StarryError::CyclicDependency` if systems in a priority group depend on each other
+    pub(crate) fn execution_plan(&self) -> Result<ExecutionPlan, StarryError> {
+        let groups = self.system_priorities().into_iter().map(|priority| {
+            let systems = self.systems.get(&priority).map(Vec::as_slice).unwrap_or_default();
+            let batches = systems::dependency_layers(priority, systems, &self.system_dependencies)?;
+            let exclusive = self.exclusive_systems.get(&priority).cloned().unwrap_or_default();
+            Ok((priority, batches, exclusive))
+        }).collect::<Result<_, StarryError>>()?;
+
+        let frame_intervals = self.frame_intervals.iter().map(|((priority, system), intervals)| {
+            let occurrences = self.systems.get(priority).map_or(0, |systems| systems.iter().filter(|s| std::ptr::fn_addr_eq(**s, *system)).count());
+            ((*priority, *system), (occurrences.saturating_sub(intervals.len()), intervals.clone()))
+        }).collect::<HashMap<_, _>>();
+        let started = frame_intervals.keys().map(|key| (*key, AtomicUsize::new(0))).collect();
+
+        Ok(ExecutionPlan { groups, run_conditions: self.run_conditions.clone(), frame_intervals, started })
+    }
+}
@@ -0,0 +1,191 @@
+use std::any::{TypeId, type_name};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::{RwLock, RwLockReadGuard};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::World;
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::resources::Resource;
+use crate::snapshot::link_aliases;
+
+/// Marker trait for components and resources that are included in `World::to_json`
+pub trait Serializable: Serialize + DeserializeOwned {}
+
+type ComponentSerializer = fn(&dyn Component) -> Result<Value, serde_json::Error>;
+type ComponentDeserializer = fn(Value) -> Result<Arc<RwLock<dyn Component>>, serde_json::Error>;
+type ResourceSerializer = fn(&dyn Resource) -> Result<Value, serde_json::Error>;
+type ResourceDeserializer = fn(Value) -> Result<Arc<RwLock<dyn Resource>>, serde_json::Error>;
+
+/// Serializers for every type registered with `World::register_serializable_component`
+/// and `World::register_serializable_resource`
+#[derive(Clone, Default)]
+pub(crate) struct Serializers {
+    components: HashMap<TypeId, (&'static str, ComponentSerializer, ComponentDeserializer)>,
+    resources: HashMap<TypeId, (&'static str, ResourceSerializer, ResourceDeserializer)>,
+}
+
+/// A component in the document made by `World::to_json`
+#[derive(serde::Deserialize)]
+struct SerializedComponent {
+    entity: Option<Entity>,
+    value: Value
+}
+
+fn serialize_component<T: Component + Serializable + 'static>(component: &dyn Component) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(unsafe { &*(component as *const dyn Component as *const T) })
+}
+
+fn deserialize_component<T: Component + Serializable + 'static>(value: Value) -> Result<Arc<RwLock<dyn Component>>, serde_json::Error> {
+    Ok(Arc::new(RwLock::new(serde_json::from_value::<T>(value)?)))
+}
+
+fn serialize_resource<T: Resource + Serializable + 'static>(resource: &dyn Resource) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(unsafe { &*(resource as *const dyn Resource as *const T) })
+}
+
+fn deserialize_resource<T: Resource + Serializable + 'static>(value: Value) -> Result<Arc<RwLock<dyn Resource>>, serde_json::Error> {
+    Ok(Arc::new(RwLock::new(serde_json::from_value::<T>(value)?)))
+}
+
+impl World {
+    /// Serializes every component of type `T` into a JSON string
+    ///
+    /// # Errors
+    /// Will return a `serde_json::Error` if a component fails to serialize
+    pub fn serialize_components<T: Component + Serialize + 'static>(&self) -> Result<Vec<String>, serde_json::Error> {
+        let id = TypeId::of::<T>();
+
+        self.components
+            .iter()
+            .filter(|(_, t, _)| t == &id)
+            .map(|(v, _, _)| {
+                let component = RwLockReadGuard::map(v.read(), |r| {
+                    unsafe { &*(r as *const dyn Component as *const T) }
+                });
+                serde_json::to_string(&*component)
+            })
+            .collect()
+    }
+
+    /// Deserializes JSON strings into components of type `T` and adds them to the world
+    ///
+    /// # Errors
+    /// Will return a `serde_json::Error` if a component fails to deserialize, no components are added in that case
+    pub fn deserialize_components<T: Component + DeserializeOwned + 'static>(&mut self, components: &[String]) -> Result<&mut Self, serde_json::Error> {
+        let components = components.iter().map(|c| serde_json::from_str::<T>(c)).collect::<Result<Vec<_>, _>>()?;
+        for component in components {
+            self.add_component(component);
+        }
+        Ok(self)
+    }
+
+    /// Includes components of type `T` in `to_json` and `from_json`
+    pub fn register_serializable_component<T: Component + Serializable + 'static>(&mut self) -> &mut Self {
+        self.serializers.components.insert(TypeId::of::<T>(), (type_name::<T>(), serialize_component::<T>, deserialize_component::<T>));
+        self.register_component_type::<T>();
+        self
+    }
+
+    /// Includes the resource of type `T` in `to_json` and `from_json`
+    pub fn register_serializable_resource<T: Resource + Serializable + 'static>(&mut self) -> &mut Self {
+        self.serializers.resources.insert(TypeId::of::<T>(), (type_name::<T>(), serialize_resource::<T>, deserialize_resource::<T>));
+        self.register_resource_type::<T>();
+        self
+    }
+
+    /// Serializes every registered component and resource into a JSON document
+    ///
+    /// # Errors
+    /// Will return a `serde_json::Error` if a component or resource fails to serialize
+    /// # Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::serialization::Serializable;
+    ///
+    /// #[derive(Clone, Debug, Serialize, Deserialize)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    /// impl Serializable for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_serializable_component::<Position>().add_component(Position { x: 4 });
+    /// let json = world.to_json().unwrap();
+    ///
+    /// let mut loaded = World::new();
+    /// loaded.register_serializable_component::<Position>().from_json(&json).unwrap();
+    /// assert_eq!(loaded.get_components::<Position>()[0].x, 4);
+    /// ```
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let mut components = Map::new();
+        for (id, (name, serialize, _)) in self.serializers.components.iter() {
+            let values = self.components
+                .iter()
+                .filter(|(_, t, _)| t == id)
+                .map(|(v, _, entity)| Ok(serde_json::json!({ "entity": entity, "value": serialize(&*v.read())? })))
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            components.insert(name.to_string(), Value::Array(values));
+        }
+
+        let mut resources = Map::new();
+        for (id, (name, serialize, _)) in self.serializers.resources.iter() {
+            if let Some(resource) = self.resources.get(id) {
+                resources.insert(name.to_string(), serialize(&*resource.read())?);
+            }
+        }
+
+        let mut world = Map::new();
+        world.insert("components".to_string(), Value::Object(components));
+        world.insert("resources".to_string(), Value::Object(resources));
+        serde_json::to_string(&world)
+    }
+
+    /// Loads components and resources from a JSON document made by `to_json`.
+    /// Components of registered types are replaced and resources of registered types are overwritten,
+    /// types that aren't registered are skipped. Replaced and loaded components go through
+    /// `Component::on_remove`, `Component::on_add` and the hooks from `with_component_hook`.
+    ///
+    /// # Errors
+    /// Will return a `serde_json::Error` if the document is malformed, the world is left unchanged in that case
+    pub fn from_json(&mut self, json: &str) -> Result<&mut Self, serde_json::Error> {
+        let mut world: Map<String, Value> = serde_json::from_str(json)?;
+        let mut components: Map<String, Value> = serde_json::from_value(world.remove("components").unwrap_or(Value::Object(Map::new())))?;
+        let mut resources: Map<String, Value> = serde_json::from_value(world.remove("resources").unwrap_or(Value::Object(Map::new())))?;
+
+        let mut loaded_components = vec![];
+        for (id, (name, _, deserialize)) in self.serializers.components.iter() {
+            let values: Vec<SerializedComponent> = match components.remove(*name) {
+                Some(values) => serde_json::from_value(values)?,
+                None => continue
+            };
+            for SerializedComponent { entity, value } in values {
+                loaded_components.push((deserialize(value)?, *id, entity));
+            }
+        }
+
+        let mut loaded_resources = vec![];
+        for (id, (name, _, deserialize)) in self.serializers.resources.iter() {
+            if let Some(value) = resources.remove(*name) {
+                loaded_resources.push((*id, deserialize(value)?));
+            }
+        }
+
+        let registered = self.serializers.components.keys().copied().collect::<Vec<_>>();
+        self.take_components(|t, _| registered.contains(&t));
+        for entity in loaded_components.iter().filter_map(|(_, _, e)| *e) {
+            self.entity_allocator.reserve(entity);
+        }
+        self.notify_added(&loaded_components);
+        self.components.extend(loaded_components);
+        self.rebuild_entity_index();
+        self.resources.extend(loaded_resources);
+        link_aliases(&mut self.resources, &self.resource_aliases);
+        Ok(self)
+    }
+}
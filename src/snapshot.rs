@@ -0,0 +1,481 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::{StarryError, World};
+use crate::component::{self, Component, ComponentCloner, ComponentEq, ComponentStorage};
+use crate::entity::{Entity, EntityAllocator};
+use crate::hierarchy::Parent;
+use crate::non_send::NonSendStorage;
+use crate::resources::{self, Resource, ResourceCloner, ResourceEq};
+use crate::schedule::Schedule;
+
+/// A point-in-time copy of the components and resources of a `World`
+pub struct WorldSnapshot {
+    pub(crate) components: ComponentStorage,
+    pub(crate) resources: HashMap<TypeId, Arc<RwLock<dyn Resource>>>,
+    pub(crate) component_cloners: HashMap<TypeId, ComponentCloner>,
+    pub(crate) component_names: HashMap<TypeId, &'static str>,
+    pub(crate) resource_cloners: HashMap<TypeId, ResourceCloner>,
+    pub(crate) resource_names: HashMap<TypeId, &'static str>,
+    pub(crate) resource_aliases: HashMap<TypeId, TypeId>,
+    pub(crate) component_eqs: HashMap<TypeId, ComponentEq>,
+    pub(crate) resource_eqs: HashMap<TypeId, ResourceEq>,
+    pub(crate) entity_allocator: EntityAllocator,
+    pub(crate) frame: u64,
+}
+
+impl WorldSnapshot {
+    /// The frame the snapshot was taken at
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+}
+
+/// Points every alias at the resource it shares, if that resource is in `resources`
+pub(crate) fn link_aliases(resources: &mut HashMap<TypeId, Arc<RwLock<dyn Resource>>>, aliases: &HashMap<TypeId, TypeId>) {
+    for (alias, canonical) in aliases {
+        if let Some(resource) = resources.get(canonical).cloned() {
+            resources.insert(*alias, resource);
+        }
+    }
+}
+
+impl World {
+    /// Deep clones every component into new locks
+    pub(crate) fn cloned_components(&self) -> ComponentStorage {
+        self.components.iter().map(|(component, id, entity)| {
+            (self.component_cloners[id](&*component.read()), *id, *entity)
+        }).collect()
+    }
+
+    /// Deep clones every resource into new locks, aliases are pointed at the copy of the resource they share
+    pub(crate) fn cloned_resources(&self) -> HashMap<TypeId, Arc<RwLock<dyn Resource>>> {
+        let mut resources = self.unaliased_resources().map(|(id, resource)| {
+            (*id, self.resource_cloners[id](&*resource.read()))
+        }).collect();
+        link_aliases(&mut resources, &self.resource_aliases);
+        resources
+    }
+
+    /// Lets `WorldDiff` compare components of type `T` with `PartialEq`, so unchanged ones are left out of diffs
+    pub fn register_diffable_component<T: Component + PartialEq + 'static>(&mut self) -> &mut Self {
+        self.component_eqs.insert(TypeId::of::<T>(), component::eq_erased::<T>);
+        self
+    }
+
+    /// Lets `WorldDiff` compare resources of type `T` with `PartialEq`, so unchanged ones are left out of diffs
+    pub fn register_diffable_resource<T: Resource + PartialEq + 'static>(&mut self) -> &mut Self {
+        self.resource_eqs.insert(TypeId::of::<T>(), resources::eq_erased::<T>);
+        self
+    }
+
+    /// Takes a copy of all components and resources
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Score { points: i32 }
+    /// impl Resource for Score {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Score { points: 0 });
+    ///
+    /// let snapshot = world.snapshot();
+    /// world.get_resource_mut::<Score>().points = 10;
+    /// world.restore(snapshot);
+    ///
+    /// assert_eq!(world.get_resource::<Score>().points, 0);
+    /// ```
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            components: self.cloned_components(),
+            resources: self.cloned_resources(),
+            component_cloners: self.component_cloners.clone(),
+            component_names: self.component_names.clone(),
+            resource_cloners: self.resource_cloners.clone(),
+            resource_names: self.resource_names.clone(),
+            resource_aliases: self.resource_aliases.clone(),
+            component_eqs: self.component_eqs.clone(),
+            resource_eqs: self.resource_eqs.clone(),
+            entity_allocator: self.entity_allocator.clone(),
+            frame: self.frame,
+        }
+    }
+
+    /// Replaces all components and resources with the contents of a snapshot.
+    /// Systems are left in place.
+    pub fn restore(&mut self, snapshot: WorldSnapshot) -> &mut Self {
+        self.components = snapshot.components;
+        self.rebuild_entity_index();
+        self.resources = snapshot.resources;
+        self.component_cloners.extend(snapshot.component_cloners);
+        self.component_names.extend(snapshot.component_names);
+        self.resource_cloners.extend(snapshot.resource_cloners);
+        self.resource_names.extend(snapshot.resource_names);
+        self.resource_aliases = snapshot.resource_aliases;
+        self.component_eqs.extend(snapshot.component_eqs);
+        self.resource_eqs.extend(snapshot.resource_eqs);
+        self.entity_allocator = snapshot.entity_allocator;
+        self.frame = snapshot.frame;
+        self
+    }
+
+    /// Creates a world that shares resources with this one but has its own copy of every component.
+    /// Systems are copied over as well, but their state from `get_local` starts over in the fork.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Position { x: i32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Position { x: 0 });
+    ///
+    /// let fork = world.fork();
+    /// fork.get_components_mut::<Position>()[0].x = 10;
+    /// assert_eq!(world.get_components::<Position>()[0].x, 0);
+    ///
+    /// world.merge_components_from::<Position>(&fork);
+    /// assert_eq!(world.get_components::<Position>()[0].x, 10);
+    /// ```
+    pub fn fork(&self) -> World {
+        let mut world = self.clone();
+        world.components = self.cloned_components();
+        world
+    }
+
+    /// Creates a world with its own copy of every component and resource but no systems,
+    /// so it can be inspected or stepped without running any game logic.
+    /// Observers, system locals, held component locks and the error callback aren't carried over either.
+    /// Non send resources can't be copied, so the new world starts without them.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Score { points: i32 }
+    /// impl Resource for Score {}
+    ///
+    /// fn score(world: &World) {
+    ///     world.get_resource_mut::<Score>().points += 1;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Score { points: 0 }).add_system(DefaultOrdering::Run, score);
+    ///
+    /// let mut planning = world.clone_without_systems();
+    /// planning.get_resource_mut::<Score>().points = 10;
+    /// planning.single_step();
+    ///
+    /// assert_eq!(planning.get_resource::<Score>().points, 10);
+    /// assert_eq!(world.get_resource::<Score>().points, 0);
+    /// ```
+    pub fn clone_without_systems(&self) -> World {
+        let mut world = self.clone();
+        world.components = self.cloned_components();
+        world.resources = self.cloned_resources();
+        world.schedule = Schedule::new();
+        world.param_systems.clear();
+        world.non_send_resources = NonSendStorage::default();
+        world.observers.clear();
+        world.error_callback = None;
+        world
+    }
+
+    /// Replaces every component of type `T` with a copy of the ones in `other`.
+    /// The replaced components and the copies go through `Component::on_remove`, `Component::on_add`
+    /// and the hooks from `with_component_hook` like they do when added and removed one by one.
+    pub fn merge_components_from<T: Component + 'static>(&mut self, other: &World) -> &mut Self {
+        let id = TypeId::of::<T>();
+
+        let merged = other.components.iter().filter(|(_, t, _)| t == &id).map(|(component, _, entity)| {
+            (crate::component::clone_locked::<T>(&*component.read()), id, *entity)
+        }).collect::<ComponentStorage>();
+
+        for entity in merged.iter().filter_map(|(_, _, e)| *e) {
+            if !self.entity_allocator.is_alive(entity) {
+                self.entity_allocator.reserve(entity);
+            }
+        }
+
+        self.take_components(|t, _| t == id);
+        self.notify_added(&merged);
+        self.components.extend(merged);
+        self.register_component_type::<T>();
+        self.rebuild_entity_index();
+        self
+    }
+}
+
+/// What `World::merge` does when both worlds have a resource of the same type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the resource of the world being merged into
+    KeepSelf,
+    /// Replace the resource with the one from the other world
+    KeepOther,
+    /// Fail the merge without changing either world
+    Error
+}
+
+impl World {
+    /// Moves every component and resource of `other` into this world.
+    /// Entities of `other` are given new entities in this world and `Parent` components are pointed at them,
+    /// parents that have no components in `other` are left as they are.
+    /// Merged components go through `Component::on_add` and the hooks from `with_component_hook` of this world.
+    /// Systems aren't merged, they have to be added again.
+    ///
+    /// # Errors
+    /// Will return a `StarryError::ResourceConflict` if both worlds have a resource of the same type
+    /// and `strategy` is `MergeStrategy::Error`, this world is left unchanged in that case
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::component::Component;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::snapshot::MergeStrategy;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Tile { x: i32 }
+    /// impl Component for Tile {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Seed(u64);
+    /// impl Resource for Seed {}
+    ///
+    /// let mut world = World::new();
+    /// world.add_component(Tile { x: 0 }).add_resource(Seed(1));
+    ///
+    /// let mut chunk = World::new();
+    /// chunk.add_component(Tile { x: 16 }).add_resource(Seed(2));
+    ///
+    /// assert!(world.clone().try_merge(chunk.clone(), MergeStrategy::Error).is_err());
+    ///
+    /// world.merge(chunk, MergeStrategy::KeepSelf);
+    /// assert_eq!(world.get_components::<Tile>().len(), 2);
+    /// assert_eq!(world.get_resource::<Seed>().0, 1);
+    /// ```
+    pub fn try_merge(&mut self, other: World, strategy: MergeStrategy) -> Result<&mut Self, StarryError> {
+        if strategy == MergeStrategy::Error {
+            if let Some(id) = other.resources.keys().find(|id| self.resources.contains_key(id)) {
+                return Err(StarryError::ResourceConflict(other.resource_names.get(id).copied().unwrap_or("unknown")));
+            }
+        }
+
+        let mut entities: HashMap<Entity, Entity> = HashMap::new();
+        for entity in other.components.iter().filter_map(|(_, _, e)| *e) {
+            entities.entry(entity).or_insert_with(|| self.entity_allocator.allocate());
+        }
+
+        let parent_id = TypeId::of::<Parent>();
+        let merged = other.components.into_iter().map(|(component, id, entity)| {
+            if id == parent_id {
+                let mut guard = component.write();
+                let parent = unsafe { &mut *(&mut *guard as *mut dyn Component as *mut Parent) };
+                if let Some(mapped) = entities.get(&parent.get()) {
+                    *parent = Parent::new(*mapped);
+                }
+            }
+            (component, id, entity.map(|e| entities[&e]))
+        }).collect::<ComponentStorage>();
+        self.component_cloners.extend(other.component_cloners);
+        self.component_names.extend(other.component_names);
+        self.notify_added(&merged);
+        self.components.extend(merged);
+        self.rebuild_entity_index();
+
+        for (id, resource) in other.resources {
+            if !other.resource_aliases.contains_key(&id) && (strategy == MergeStrategy::KeepOther || !self.resources.contains_key(&id)) {
+                self.resources.insert(id, resource);
+            }
+        }
+        for (alias, canonical) in other.resource_aliases {
+            self.resource_aliases.entry(alias).or_insert(canonical);
+        }
+        link_aliases(&mut self.resources, &self.resource_aliases);
+        self.resource_cloners.extend(other.resource_cloners);
+        self.resource_names.extend(other.resource_names);
+        Ok(self)
+    }
+
+    /// Same as `try_merge` but unwraps the value
+    pub fn merge(&mut self, other: World, strategy: MergeStrategy) -> &mut Self {
+        self.try_merge(other, strategy).unwrap()
+    }
+}
+
+/// For every component in `components`, the index of an equal component of the same type and entity in `other`.
+/// Each component of `other` is matched at most once. Types without an equality from `World::register_diffable_component`
+/// never match, unless `match_unknown` is set, in which case they match any component of the same type and entity.
+fn match_components(components: &ComponentStorage, other: &ComponentStorage, eqs: &HashMap<TypeId, ComponentEq>, match_unknown: bool) -> Vec<Option<usize>> {
+    let mut candidates: HashMap<(TypeId, Option<Entity>), Vec<usize>> = HashMap::new();
+    for (i, (_, id, entity)) in other.iter().enumerate() {
+        candidates.entry((*id, *entity)).or_default().push(i);
+    }
+
+    components.iter().map(|(component, id, entity)| {
+        let candidates = candidates.get_mut(&(*id, *entity))?;
+        let position = match eqs.get(id) {
+            Some(eq) => {
+                let component = component.read();
+                candidates.iter().position(|i| eq(&*component, &*other[*i].0.read()))?
+            }
+            None if match_unknown && !candidates.is_empty() => 0,
+            None => return None
+        };
+        Some(candidates.remove(position))
+    }).collect()
+}
+
+/// The changes between two snapshots, made with `WorldDiff::compute` and applied with `World::apply_diff`.
+/// Components and resources are compared with `PartialEq` if their type was registered with
+/// `World::register_diffable_component` or `World::register_diffable_resource`, otherwise they always count as changed.
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::component::Component;
+/// use starry_ecs::snapshot::WorldDiff;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: i32 }
+/// impl Component for Position {}
+///
+/// let mut world = World::new();
+/// world.register_diffable_component::<Position>().add_component(Position { x: 0 }).add_component(Position { x: 1 });
+/// let mut replica = world.fork();
+///
+/// let before = world.snapshot();
+/// world.get_components_mut::<Position>()[0].x = 5;
+/// let diff = WorldDiff::compute(&before, &world.snapshot());
+///
+/// assert_eq!(diff.removed_component_count(), 1);
+/// assert_eq!(diff.added_component_count(), 1);
+///
+/// replica.apply_diff(diff);
+/// let mut positions = replica.iter_components::<Position>().map(|p| p.x).collect::<Vec<_>>();
+/// positions.sort();
+/// assert_eq!(positions, vec![1, 5]);
+/// ```
+pub struct WorldDiff {
+    added_components: ComponentStorage,
+    removed_components: ComponentStorage,
+    modified_resources: HashMap<TypeId, Arc<RwLock<dyn Resource>>>,
+    removed_resources: Vec<TypeId>,
+    component_cloners: HashMap<TypeId, ComponentCloner>,
+    component_names: HashMap<TypeId, &'static str>,
+    resource_cloners: HashMap<TypeId, ResourceCloner>,
+    resource_names: HashMap<TypeId, &'static str>,
+    component_eqs: HashMap<TypeId, ComponentEq>,
+}
+
+impl WorldDiff {
+    /// Computes the changes that turn `before` into `after`.
+    /// A changed component shows up as the old one being removed and the new one being added.
+    pub fn compute(before: &WorldSnapshot, after: &WorldSnapshot) -> WorldDiff {
+        let matches = match_components(&after.components, &before.components, &after.component_eqs, false);
+        let added_components = after.components.iter().zip(matches.iter()).filter(|(_, matched)| matched.is_none()).map(|((component, id, entity), _)| {
+            (after.component_cloners[id](&*component.read()), *id, *entity)
+        }).collect();
+
+        let mut kept = vec![false; before.components.len()];
+        for i in matches.into_iter().flatten() {
+            kept[i] = true;
+        }
+        let removed_components = before.components.iter().zip(kept).filter(|(_, kept)| !kept).map(|((component, id, entity), _)| {
+            (before.component_cloners[id](&*component.read()), *id, *entity)
+        }).collect();
+
+        let modified_resources = after.resources.iter().filter(|(id, _)| !after.resource_aliases.contains_key(id)).filter(|(id, resource)| {
+            let (Some(old), Some(eq)) = (before.resources.get(id), after.resource_eqs.get(id)) else {
+                return true;
+            };
+            !eq(&*old.read(), &*resource.read())
+        }).map(|(id, resource)| (*id, after.resource_cloners[id](&*resource.read()))).collect();
+        let removed_resources = before.resources.keys().filter(|id| !before.resource_aliases.contains_key(id) && !after.resources.contains_key(id)).copied().collect();
+
+        WorldDiff {
+            added_components,
+            removed_components,
+            modified_resources,
+            removed_resources,
+            component_cloners: after.component_cloners.clone(),
+            component_names: after.component_names.clone(),
+            resource_cloners: after.resource_cloners.clone(),
+            resource_names: after.resource_names.clone(),
+            component_eqs: after.component_eqs.clone(),
+        }
+    }
+
+    /// Counts the components that were added
+    pub fn added_component_count(&self) -> usize {
+        self.added_components.len()
+    }
+
+    /// Counts the components that were removed
+    pub fn removed_component_count(&self) -> usize {
+        self.removed_components.len()
+    }
+
+    /// Counts the resources that were added or changed
+    pub fn modified_resource_count(&self) -> usize {
+        self.modified_resources.len()
+    }
+
+    /// Counts the resources that were removed
+    pub fn removed_resource_count(&self) -> usize {
+        self.removed_resources.len()
+    }
+
+    /// Returns `true` if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added_components.is_empty() && self.removed_components.is_empty() && self.modified_resources.is_empty() && self.removed_resources.is_empty()
+    }
+}
+
+impl World {
+    /// Applies the changes of a `WorldDiff`, removing components that match a removed component
+    /// and adding the rest. Removed components that aren't in the world are skipped.
+    /// Components of types without an equality match any component of the same type and entity.
+    /// `Component::on_add`, `Component::on_remove` and the hooks from `with_component_hook` are called like they are for components added and removed one by one.
+    pub fn apply_diff(&mut self, diff: WorldDiff) -> &mut Self {
+        let mut removed = vec![false; self.components.len()];
+        for i in match_components(&diff.removed_components, &self.components, &diff.component_eqs, true).into_iter().flatten() {
+            removed[i] = true;
+        }
+
+        let mut removed = removed.into_iter();
+        let taken = self.remove_components(|_| removed.next().unwrap());
+
+        for entity in diff.added_components.iter().filter_map(|(_, _, e)| *e) {
+            if !self.entity_allocator.is_alive(entity) {
+                self.entity_allocator.reserve(entity);
+            }
+        }
+        self.component_cloners.extend(diff.component_cloners);
+        self.component_names.extend(diff.component_names);
+        self.component_eqs.extend(diff.component_eqs);
+        self.notify_added(&diff.added_components);
+        self.components.extend(diff.added_components);
+        self.rebuild_entity_index();
+        self.notify_removed(&taken);
+
+        for id in diff.removed_resources {
+            self.resources.remove(&id);
+        }
+        self.resources.extend(diff.modified_resources);
+        link_aliases(&mut self.resources, &self.resource_aliases);
+        self.resource_cloners.extend(diff.resource_cloners);
+        self.resource_names.extend(diff.resource_names);
+        self
+    }
+}
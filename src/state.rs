@@ -0,0 +1,109 @@
+use std::fmt::Debug;
+
+use crate::resources::Resource;
+use crate::{SystemType, World};
+
+/// When a system added with `World::add_state_system` runs
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StateEvent {
+    /// Runs when the state is entered
+    OnEnter,
+    /// Runs when the state is left
+    OnExit
+}
+
+/// The current state of a state machine added with `World::add_state`, along with the state it's transitioning to
+#[derive(Clone, Debug)]
+pub struct StateResource<S: PartialEq + Clone + Debug + Send + Sync + 'static> {
+    current: S,
+    next: Option<S>,
+    systems: Vec<(S, StateEvent, SystemType)>
+}
+impl<S: PartialEq + Clone + Debug + Send + Sync + 'static> Resource for StateResource<S> {}
+
+impl<S: PartialEq + Clone + Debug + Send + Sync + 'static> StateResource<S> {
+    /// The state the world is currently in
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// The state queued with `World::transition_to`, applied at the beginning of the next step
+    pub fn next(&self) -> Option<&S> {
+        self.next.as_ref()
+    }
+}
+
+/// Applies the pending transition of state `S`, running the exit systems of the old state and the enter systems of the new one.
+/// Does nothing if the state machine was removed, for example by restoring a snapshot taken before it was added.
+fn apply_transition<S: PartialEq + Clone + Debug + Send + Sync + 'static>(world: &mut World) {
+    let (previous, current, systems) = {
+        let Ok(mut state) = world.try_get_resource_mut::<StateResource<S>>() else {
+            return;
+        };
+        let Some(next) = state.next.take() else {
+            return;
+        };
+        let previous = std::mem::replace(&mut state.current, next.clone());
+        (previous, next, state.systems.clone())
+    };
+
+    for (state, event, system) in systems.iter() {
+        if *event == StateEvent::OnExit && *state == previous {
+            system(world);
+        }
+    }
+    for (state, event, system) in systems.iter() {
+        if *event == StateEvent::OnEnter && *state == current {
+            system(world);
+        }
+    }
+}
+
+impl World {
+    /// Adds a state machine with states of type `S`, starting in `initial`.
+    /// Transitions queued with `transition_to` are applied at the beginning of every step.
+    /// If the state machine already exists, it will not be replaced.
+    ///
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::state::{StateEvent, StateResource};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum GameState { MainMenu, InGame }
+    ///
+    /// fn spawn_level(_: &World) {
+    ///     println!("Loading level");
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_state(GameState::MainMenu).add_state_system(GameState::InGame, StateEvent::OnEnter, spawn_level);
+    ///
+    /// world.transition_to(GameState::InGame);
+    /// world.single_step();
+    /// assert_eq!(world.get_resource::<StateResource<GameState>>().current(), &GameState::InGame);
+    /// ```
+    pub fn add_state<S: PartialEq + Clone + Debug + Send + Sync + 'static>(&mut self, initial: S) -> &mut Self {
+        if self.try_get_resource::<StateResource<S>>().is_err() {
+            self.state_transitions.push(apply_transition::<S>);
+        }
+        self.add_resource(StateResource { current: initial, next: None, systems: vec![] })
+    }
+
+    /// Adds a system that runs when the world enters or leaves `state`
+    ///
+    /// # Panics
+    /// Panics if `add_state` wasn't called for states of type `S`
+    pub fn add_state_system<S: PartialEq + Clone + Debug + Send + Sync + 'static>(&mut self, state: S, event: StateEvent, system: SystemType) -> &mut Self {
+        self.get_resource_mut::<StateResource<S>>().systems.push((state, event, system));
+        self
+    }
+
+    /// Queues a transition to `next`, replacing any transition queued before it.
+    /// The transition is applied at the beginning of the next step.
+    ///
+    /// # Panics
+    /// Panics if `add_state` wasn't called for states of type `S`
+    pub fn transition_to<S: PartialEq + Clone + Debug + Send + Sync + 'static>(&self, next: S) {
+        self.get_resource_mut::<StateResource<S>>().next = Some(next);
+    }
+}
@@ -0,0 +1,192 @@
+use std::any::{Any, TypeId, type_name};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::query::{Query, QueryFilter, QueryResult};
+use crate::resources::Resource;
+use crate::systems::{SystemMeta, SystemOrdering};
+use crate::{ResourceReadGuard, ResourceWriteGuard, SystemType, World};
+
+/// Something a system can take as an argument, fetched from the world right before the system runs
+pub trait SystemParam {
+    /// The parameter fetched from a world that lives for `'w`
+    type Item<'w>;
+
+    /// Fetches the parameter from the world
+    fn fetch(world: &World) -> Self::Item<'_>;
+
+    /// Declares what the parameter reads and writes
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        meta
+    }
+}
+
+/// The item a `SystemParam` fetches from a world that lives for `'w`
+pub type SystemParamItem<'w, P> = <P as SystemParam>::Item<'w>;
+
+/// Read access to a resource as a system parameter
+pub struct Res<'w, T: Resource + 'static>(ResourceReadGuard<'w, T>);
+
+impl<T: Resource + 'static> Deref for Res<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Resource + 'static> SystemParam for Res<'_, T> {
+    type Item<'w> = Res<'w, T>;
+
+    fn fetch(world: &World) -> Self::Item<'_> {
+        Res(world.get_resource::<T>())
+    }
+
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        meta.reads::<T>()
+    }
+}
+
+/// Write access to a resource as a system parameter
+pub struct ResMut<'w, T: Resource + 'static>(ResourceWriteGuard<'w, T>);
+
+impl<T: Resource + 'static> Deref for ResMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Resource + 'static> DerefMut for ResMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Resource + 'static> SystemParam for ResMut<'_, T> {
+    type Item<'w> = ResMut<'w, T>;
+
+    fn fetch(world: &World) -> Self::Item<'_> {
+        ResMut(world.get_resource_mut::<T>())
+    }
+
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        meta.writes::<T>()
+    }
+}
+
+impl<Q: Query, F: QueryFilter> SystemParam for QueryResult<'_, Q, F> {
+    type Item<'w> = QueryResult<'w, Q, F>;
+
+    fn fetch(world: &World) -> Self::Item<'_> {
+        world.query_filtered::<Q, F>()
+    }
+
+    fn describe(meta: SystemMeta) -> SystemMeta {
+        Q::describe(meta)
+    }
+}
+
+/// A function whose arguments are all `SystemParam`s, implemented for functions with up to four parameters.
+/// `Params` only tells the implementations apart.
+pub trait System<Params>: Send + Sync + 'static {
+    /// Fetches every parameter and calls the function
+    fn run(&self, world: &World);
+
+    /// Declares what the function reads and writes through its parameters
+    fn describe(meta: SystemMeta) -> SystemMeta;
+}
+
+macro_rules! impl_system {
+    ($($param:ident),*) => {
+        #[allow(non_snake_case, unused_variables)]
+        impl<Func, $($param: SystemParam),*> System<fn($($param,)*)> for Func
+        where
+            Func: Fn($($param),*) + Fn($(SystemParamItem<'_, $param>),*) + Send + Sync + 'static
+        {
+            fn run(&self, world: &World) {
+                // Calling through a generic function lets the compiler pick the `Fn` bound with the fetched lifetime
+                fn call<$($param),*>(f: impl Fn($($param),*), $($param: $param),*) {
+                    f($($param),*)
+                }
+                $(let $param = $param::fetch(world);)*
+                call(self, $($param),*)
+            }
+
+            fn describe(meta: SystemMeta) -> SystemMeta {
+                $(let meta = $param::describe(meta);)*
+                meta
+            }
+        }
+    };
+}
+
+impl_system!();
+impl_system!(A);
+impl_system!(A, B);
+impl_system!(A, B, C);
+impl_system!(A, B, C, D);
+
+/// Runs the function of type `F` stored by `World::add_param_system`
+fn run_param_system<Params: 'static, F: System<Params>>(world: &World) {
+    let system = world.param_systems.get(&TypeId::of::<F>()).expect("Parameter system was added to another world");
+    let system: &(dyn Any + Send + Sync) = &**system;
+    system.downcast_ref::<F>().unwrap().run(world);
+}
+
+impl World {
+    /// Panics if `meta` writes a type that it also reads or writes somewhere else, since locking it twice would deadlock
+    pub(crate) fn check_exclusive_access(&self, meta: &SystemMeta) {
+        let reads = meta.read_ids().map(|id| self.canonical_resource_id(id)).collect::<Vec<_>>();
+        let writes = meta.write_ids().map(|id| self.canonical_resource_id(id)).collect::<Vec<_>>();
+        for (i, id) in writes.iter().enumerate() {
+            assert!(!writes[..i].contains(id) && !reads.contains(id), "`{}` takes write access to a type it already accesses", meta.name());
+        }
+    }
+
+    /// Adds a system whose arguments are fetched from the world before it runs, instead of taking the world itself.
+    /// What the system reads and writes is declared with `describe_system` for `validate_no_conflicts`.
+    /// Returns the `SystemType` the function runs as, for `add_system_after` and the like.
+    ///
+    /// # Panics
+    /// Panics if the system takes write access to a type that another of its parameters reads or writes, which would deadlock.
+    /// Rust can't tell two generic types apart at compile time, so this is checked when the system is added instead of by the compiler.
+    /// Aliases from `add_resource_alias` count as the resource they share, so they have to be added before the system.
+    /// # Example
+    /// ```
+    /// use starry_ecs::World;
+    /// use starry_ecs::resources::Resource;
+    /// use starry_ecs::system_param::{Res, ResMut};
+    /// use starry_ecs::systems::DefaultOrdering;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Gravity(i32);
+    /// impl Resource for Gravity {}
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Height(i32);
+    /// impl Resource for Height {}
+    ///
+    /// fn fall(gravity: Res<Gravity>, mut height: ResMut<Height>) {
+    ///     height.0 -= gravity.0;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_resource(Gravity(2)).add_resource(Height(10));
+    /// world.add_param_system(DefaultOrdering::Run, fall);
+    /// world.single_step();
+    ///
+    /// assert_eq!(world.get_resource::<Height>().0, 8);
+    /// ```
+    pub fn add_param_system<S: SystemOrdering + Copy, Params: 'static, F: System<Params>>(&mut self, system_ordering: S, system: F) -> SystemType {
+        let meta = F::describe(SystemMeta::new(type_name::<F>()));
+        self.check_exclusive_access(&meta);
+
+        let run: SystemType = run_param_system::<Params, F>;
+        self.param_systems.insert(TypeId::of::<F>(), Arc::new(system));
+        self.describe_system(run, meta);
+        self.add_system(system_ordering, run);
+        run
+    }
+}
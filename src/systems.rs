@@ -1,5 +1,18 @@
+use std::any::{TypeId, type_name};
+use std::collections::HashMap;
+
+use crate::{StarryError, SystemType};
+
 /// A marker trait to say what is an enum for SystemOrdering
-pub trait SystemOrdering: Into<i32> + Copy {}
+pub trait SystemOrdering: Into<i32> + Copy {
+    /// Systems that must finish before a system added with this ordering runs.
+    ///
+    /// Only dependencies in the same priority group are considered, systems in
+    /// earlier groups already run before it.
+    fn dependencies(&self) -> Vec<SystemType> {
+        vec![]
+    }
+}
 
 /// A default enum for SystemOrdering
 #[repr(i32)]
@@ -13,9 +26,137 @@ pub enum DefaultOrdering {
     PostRun = 3
 }
 
-impl Into<i32> for DefaultOrdering {
-    fn into(self) -> i32 {
-        self as i32
+impl From<DefaultOrdering> for i32 {
+    fn from(ordering: DefaultOrdering) -> i32 {
+        ordering as i32
+    }
+}
+impl SystemOrdering for DefaultOrdering {}
+
+/// A named stage registered with `World::add_stage`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stage {
+    /// The name of the stage
+    pub name: &'static str,
+    /// The priority systems in the stage run at
+    pub priority: i32
+}
+
+impl From<Stage> for i32 {
+    fn from(stage: Stage) -> i32 {
+        stage.priority
+    }
+}
+impl SystemOrdering for Stage {}
+
+/// Declares what a system reads and writes, used by `World::validate_no_conflicts`
+///
+/// ```
+/// use starry_ecs::World;
+/// use starry_ecs::component::Component;
+/// use starry_ecs::systems::{DefaultOrdering, SystemMeta};
+///
+/// #[derive(Clone, Debug)]
+/// struct Position { x: i32 }
+/// impl Component for Position {}
+///
+/// fn movement(_: &World) {}
+/// fn teleport(_: &World) {}
+///
+/// let mut world = World::new();
+/// world
+///     .add_system(DefaultOrdering::Run, movement)
+///     .add_system(DefaultOrdering::Run, teleport)
+///     .describe_system(movement, SystemMeta::new("movement").writes::<Position>())
+///     .describe_system(teleport, SystemMeta::new("teleport").writes::<Position>());
+///
+/// assert_eq!(world.validate_no_conflicts().len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SystemMeta {
+    name: &'static str,
+    reads: Vec<(TypeId, &'static str)>,
+    writes: Vec<(TypeId, &'static str)>
+}
+
+impl SystemMeta {
+    /// Creates metadata for a system that doesn't access anything yet
+    pub fn new(name: &'static str) -> Self {
+        Self { name, ..Self::default() }
+    }
+
+    /// Declares that the system reads components or a resource of type `T`
+    pub fn reads<T: 'static>(mut self) -> Self {
+        self.reads.push((TypeId::of::<T>(), type_name::<T>()));
+        self
+    }
+
+    /// Declares that the system writes components or a resource of type `T`
+    pub fn writes<T: 'static>(mut self) -> Self {
+        self.writes.push((TypeId::of::<T>(), type_name::<T>()));
+        self
+    }
+
+    /// The name of the system
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Every type the system reads
+    pub(crate) fn read_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.reads.iter().map(|(id, _)| *id)
+    }
+
+    /// Every type the system writes
+    pub(crate) fn write_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.writes.iter().map(|(id, _)| *id)
+    }
+
+    /// Describes every way this system and `other` can block each other when run in parallel
+    pub(crate) fn conflicts_with(&self, other: &SystemMeta) -> Vec<String> {
+        let mut conflicts = vec![];
+        for (id, name) in self.writes.iter() {
+            if other.writes.iter().any(|(other_id, _)| other_id == id) {
+                conflicts.push(format!("System {} and {} both write to {}", self.name, other.name, name));
+            } else if other.reads.iter().any(|(other_id, _)| other_id == id) {
+                conflicts.push(format!("System {} writes to {} while {} reads it", self.name, name, other.name));
+            }
+        }
+        for (id, name) in self.reads.iter() {
+            if other.writes.iter().any(|(other_id, _)| other_id == id) && !self.writes.iter().any(|(own_id, _)| own_id == id) {
+                conflicts.push(format!("System {} writes to {} while {} reads it", other.name, name, self.name));
+            }
+        }
+        conflicts
+    }
+}
+
+/// Batches of systems where every system in a batch can run in parallel
+pub(crate) type SystemBatches = Vec<Vec<SystemType>>;
+
+/// Splits a priority group into layers that can each run in parallel.
+///
+/// Every system in a layer only depends on systems from earlier layers.
+pub(crate) fn dependency_layers(priority: i32, systems: &[SystemType], dependencies: &HashMap<SystemType, Vec<SystemType>>) -> Result<SystemBatches, StarryError> {
+    let mut remaining = systems.to_vec();
+    let mut finished: Vec<SystemType> = vec![];
+    let mut layers = vec![];
+
+    while !remaining.is_empty() {
+        let (layer, blocked): (Vec<SystemType>, Vec<SystemType>) = remaining.iter().partition(|system| {
+            dependencies.get(*system).is_none_or(|deps| {
+                deps.iter().all(|dep| !systems.contains(dep) || finished.contains(dep))
+            })
+        });
+
+        if layer.is_empty() {
+            return Err(StarryError::CyclicDependency(priority));
+        }
+
+        finished.extend(layer.iter().copied());
+        layers.push(layer);
+        remaining = blocked;
     }
+
+    Ok(layers)
 }
-impl SystemOrdering for DefaultOrdering {}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::component::Component;
+use crate::resources::Resource;
+use crate::systems::DefaultOrdering;
+use crate::World;
+
+/// The time between the start of the last step and the start of this one, added by `World::add_timer_support`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeltaTime {
+    /// Time passed since the last step, zero on the first step
+    pub delta: Duration,
+    last_update: Option<Instant>
+}
+impl Resource for DeltaTime {}
+
+/// Counts up to a duration, once or repeatedly, advanced by `advance_timers_system`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timer {
+    /// How long the timer runs for
+    pub duration: Duration,
+    /// How long the timer has been running since it was started or last repeated
+    pub elapsed: Duration,
+    /// Whether the timer starts over once it finishes
+    pub repeat: bool,
+    /// Whether the timer finished, only for the step it finished in if it repeats
+    pub finished: bool
+}
+impl Component for Timer {}
+
+impl Timer {
+    /// Creates a timer that hasn't started counting yet
+    pub fn new(duration: Duration, repeat: bool) -> Self {
+        Self { duration, elapsed: Duration::ZERO, repeat, finished: false }
+    }
+
+    /// Advances the timer by `delta`
+    pub fn tick(&mut self, delta: Duration) {
+        if self.finished && !self.repeat {
+            return;
+        }
+
+        self.finished = false;
+        self.elapsed += delta;
+        if self.elapsed >= self.duration {
+            self.finished = true;
+            self.elapsed = match self.repeat {
+                true if !self.duration.is_zero() => Duration::from_nanos((self.elapsed.as_nanos() % self.duration.as_nanos()) as u64),
+                _ => self.duration
+            };
+        }
+    }
+}
+
+/// Timers that don't belong to an entity, looked up by name
+#[derive(Clone, Debug, Default)]
+pub struct Timers {
+    timers: HashMap<&'static str, Timer>
+}
+impl Resource for Timers {}
+
+impl Timers {
+    /// Adds a timer, replacing any timer with the same name
+    pub fn insert(&mut self, name: &'static str, timer: Timer) {
+        self.timers.insert(name, timer);
+    }
+
+    /// Gets a timer by its name
+    pub fn get(&self, name: &'static str) -> Option<&Timer> {
+        self.timers.get(name)
+    }
+
+    /// Removes a timer by its name and returns it
+    pub fn remove(&mut self, name: &'static str) -> Option<Timer> {
+        self.timers.remove(name)
+    }
+
+    /// Whether the timer with the given name finished, `false` if there isn't one
+    pub fn finished(&self, name: &'static str) -> bool {
+        self.timers.get(name).is_some_and(|timer| timer.finished)
+    }
+}
+
+/// Updates `DeltaTime` with the time passed since it was last updated
+pub fn update_delta_time_system(world: &World) {
+    let mut delta_time = world.get_resource_mut::<DeltaTime>();
+    let now = Instant::now();
+    delta_time.delta = delta_time.last_update.map_or(Duration::ZERO, |last| now - last);
+    delta_time.last_update = Some(now);
+}
+
+/// Advances every `Timer` component and every timer in `Timers` by `DeltaTime`
+pub fn advance_timers_system(world: &World) {
+    let delta = world.get_resource::<DeltaTime>().delta;
+    world.apply_fn_to_components(|timer: &mut Timer| timer.tick(delta));
+    if let Ok(mut timers) = world.try_get_resource_mut::<Timers>() {
+        for timer in timers.timers.values_mut() {
+            timer.tick(delta);
+        }
+    }
+}
+
+impl World {
+    /// Adds the `DeltaTime` and `Timers` resources, and systems that keep them and every `Timer` up to date.
+    /// Timers are advanced in `DefaultOrdering::PreRun`, so other systems see finished timers in the same step.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use starry_ecs::World;
+    /// use starry_ecs::time::{Timer, Timers};
+    ///
+    /// let mut world = World::new();
+    /// world.add_timer_support().spawn().with(Timer::new(Duration::from_secs(5), false));
+    /// world.get_resource_mut::<Timers>().insert("spawn_wave", Timer::new(Duration::ZERO, true));
+    ///
+    /// world.single_step();
+    /// assert!(world.get_resource::<Timers>().finished("spawn_wave"));
+    /// assert!(!world.get_components::<Timer>()[0].finished);
+    /// ```
+    pub fn add_timer_support(&mut self) -> &mut Self {
+        self.add_resource(DeltaTime::default())
+            .add_resource(Timers::default())
+            .add_system(DefaultOrdering::PreRun, update_delta_time_system)
+            .add_system_after(DefaultOrdering::PreRun, update_delta_time_system, advance_timers_system)
+    }
+}
@@ -0,0 +1,224 @@
+//! # Starry ECS Derive
+//!
+//! Derive macros for the `Component` and `Resource` traits of Starry ECS
+
+#![deny(rust_2018_idioms)]
+#![deny(missing_docs)]
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+
+/// Derives `Component`, along with `Clone` and `Debug`.
+///
+/// Add `#[starry(skip_clone)]` or `#[starry(skip_debug)]` when `Clone` or `Debug` are implemented some other way.
+#[proc_macro_derive(Component, attributes(starry))]
+pub fn derive_component(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_marker(input, quote!(::starry_ecs::component::Component)).into()
+}
+
+/// Derives `Resource`, along with `Clone` and `Debug`.
+///
+/// Add `#[starry(skip_clone)]` or `#[starry(skip_debug)]` when `Clone` or `Debug` are implemented some other way.
+#[proc_macro_derive(Resource, attributes(starry))]
+pub fn derive_resource(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_marker(input, quote!(::starry_ecs::resources::Resource)).into()
+}
+
+/// Options set through `#[starry(...)]`
+#[derive(Default)]
+struct Options {
+    skip_clone: bool,
+    skip_debug: bool
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("starry")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip_clone") {
+                options.skip_clone = true;
+                Ok(())
+            } else if meta.path.is_ident("skip_debug") {
+                options.skip_debug = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip_clone` or `skip_debug`"))
+            }
+        })?;
+    }
+    Ok(options)
+}
+
+fn derive_marker(input: DeriveInput, marker: TokenStream) -> TokenStream {
+    let options = match parse_options(&input) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error()
+    };
+
+    if let Data::Union(data) = &input.data {
+        return quote_spanned! { data.union_token.span() =>
+            compile_error!("Clone and Debug can't be derived for unions, implement them manually and add #[starry(skip_clone, skip_debug)]");
+        };
+    }
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.push(syn::parse_quote!(Self: ::std::clone::Clone + ::std::fmt::Debug));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut output = quote! {
+        impl #impl_generics #marker for #name #ty_generics #where_clause {}
+    };
+
+    if !options.skip_clone {
+        output.extend(derive_clone(&input));
+    }
+    if !options.skip_debug {
+        output.extend(derive_debug(&input));
+    }
+    output
+}
+
+/// Every field of the item, used to check that they can be cloned
+fn all_fields(data: &Data) -> Vec<&syn::Field> {
+    match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(_) => vec![]
+    }
+}
+
+/// Destructures the fields of a struct or variant into bindings named `field_0`, `field_1`, ...
+fn bind_fields(fields: &Fields) -> (TokenStream, Vec<syn::Ident>) {
+    let bindings = (0..fields.len()).map(|i| format_ident!("field_{}", i)).collect::<Vec<_>>();
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(( #(#bindings),* )),
+        Fields::Unit => quote!()
+    };
+    (pattern, bindings)
+}
+
+/// Rebuilds a struct or variant from the bindings of `bind_fields`
+fn build_fields(fields: &Fields, values: &[TokenStream]) -> TokenStream {
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!({ #(#names: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(( #(#values),* )),
+        Fields::Unit => quote!()
+    }
+}
+
+fn derive_clone(input: &DeriveInput) -> TokenStream {
+    for field in all_fields(&input.data) {
+        if let Type::Reference(reference) = &field.ty {
+            if reference.mutability.is_some() {
+                return quote_spanned! { field.ty.span() =>
+                    compile_error!("Clone can't be derived for a `&mut` field, implement Clone manually and add #[starry(skip_clone)]");
+                };
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(::std::clone::Clone));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, bindings) = bind_fields(&data.fields);
+            let values = bindings.iter().map(|b| quote!(::std::clone::Clone::clone(#b))).collect::<Vec<_>>();
+            let built = build_fields(&data.fields, &values);
+            quote! {
+                let #name #pattern = self;
+                #name #built
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                let (pattern, bindings) = bind_fields(&variant.fields);
+                let values = bindings.iter().map(|b| quote!(::std::clone::Clone::clone(#b))).collect::<Vec<_>>();
+                let built = build_fields(&variant.fields, &values);
+                quote!(#name::#variant_name #pattern => #name::#variant_name #built)
+            });
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => unreachable!()
+    };
+
+    quote! {
+        impl #impl_generics ::std::clone::Clone for #name #ty_generics #where_clause {
+            #[allow(clippy::clone_on_copy)]
+            fn clone(&self) -> Self {
+                #body
+            }
+        }
+    }
+}
+
+fn derive_debug(input: &DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(::std::fmt::Debug));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let debug_fields = |label: String, fields: &Fields, bindings: &[syn::Ident]| match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| f.ident.as_ref().unwrap().to_string());
+            quote!(f.debug_struct(#label) #(.field(#names, #bindings))* .finish())
+        }
+        Fields::Unnamed(_) => quote!(f.debug_tuple(#label) #(.field(#bindings))* .finish()),
+        Fields::Unit => quote!(f.write_str(#label))
+    };
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, bindings) = bind_fields(&data.fields);
+            let debug = debug_fields(name.to_string(), &data.fields, &bindings);
+            quote! {
+                let #name #pattern = self;
+                #debug
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                let (pattern, bindings) = bind_fields(&variant.fields);
+                let debug = debug_fields(variant_name.to_string(), &variant.fields, &bindings);
+                quote!(#name::#variant_name #pattern => #debug)
+            });
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => unreachable!()
+    };
+
+    quote! {
+        impl #impl_generics ::std::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #body
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use starry_ecs::{component::Component, World, resources::Resource, systems::DefaultOrdering};
+use starry_ecs::{component::Component, World, systems::DefaultOrdering};
 
 #[derive(Clone, Debug)]
 struct TestComponent {
@@ -7,12 +7,6 @@ struct TestComponent {
 
 impl Component for TestComponent {}
 
-#[derive(Clone, Debug)]
-struct RunCounter {
-    runs: usize,
-}
-impl Resource for RunCounter {}
-
 fn test_system(world: &World) {
     let test_comp = &world.try_get_components::<TestComponent>().unwrap()[0];
 
@@ -23,3 +17,88 @@ fn test_system(world: &World) {
 fn create_component() {
     let _world = World::new().add_component(TestComponent { x: -100 }).add_system(DefaultOrdering::Run, test_system).start().single_step();
 }
+
+#[derive(Clone, Debug)]
+struct Lifecycle {
+    removed: usize
+}
+impl starry_ecs::resources::Resource for Lifecycle {}
+
+#[derive(Clone, Debug)]
+struct Tracked {
+    added: bool,
+    keep: bool
+}
+
+impl Component for Tracked {
+    fn on_add(&mut self, world: &World) {
+        self.added = world.try_get_resource::<Lifecycle>().is_ok();
+    }
+
+    fn on_remove(&mut self, world: &World) {
+        world.get_resource_mut::<Lifecycle>().removed += 1;
+    }
+}
+
+#[test]
+fn lifecycle_hooks() {
+    let mut world = World::new();
+    world.add_resource(Lifecycle { removed: 0 });
+    let entity = world.spawn().with(Tracked { added: false, keep: false }).id();
+    assert!(world.get_component_by_entity::<Tracked>(entity).added);
+
+    world.despawn(entity);
+    assert_eq!(world.get_resource::<Lifecycle>().removed, 1);
+
+    world.add_component(Tracked { added: false, keep: true }).add_component(Tracked { added: false, keep: false });
+    world.retain_components(|tracked: &Tracked| tracked.keep);
+    assert_eq!(world.get_resource::<Lifecycle>().removed, 2);
+    assert_eq!(world.get_components::<Tracked>().len(), 1);
+}
+
+#[derive(Clone, Debug)]
+struct HookCounter {
+    added: usize,
+    removed: usize
+}
+impl starry_ecs::resources::Resource for HookCounter {}
+
+fn count_added(_: &TestComponent, world: &World) {
+    world.get_resource_mut::<HookCounter>().added += 1;
+}
+
+fn count_removed(_: &TestComponent, world: &World) {
+    world.get_resource_mut::<HookCounter>().removed += 1;
+}
+
+#[test]
+fn external_component_hooks() {
+    let mut world = World::new();
+    world.add_resource(HookCounter { added: 0, removed: 0 }).with_component_hook(count_added, count_removed);
+
+    let entity = world.spawn().with(TestComponent { x: 0 }).id();
+    world.add_component(TestComponent { x: 1 }).despawn(entity);
+
+    let counter = world.get_resource::<HookCounter>();
+    assert_eq!((counter.added, counter.removed), (2, 1));
+}
+
+#[test]
+fn held_components_stay_until_released() {
+    let mut world = World::new();
+    let entity = world.spawn().with(TestComponent { x: 1 }).id();
+    world.add_component(TestComponent { x: 2 });
+
+    let held = world.component_ptr::<TestComponent>(0).unwrap();
+    let clone = world.clone();
+    assert!(!clone.release_component_ptr(held));
+
+    world.despawn(entity);
+    world.retain_components(|_: &TestComponent| false);
+    assert_eq!(world.get_components::<TestComponent>().len(), 1);
+    assert_eq!(unsafe { (*held).x }, 1);
+
+    assert!(world.release_component_ptr(held));
+    world.retain_components(|_: &TestComponent| false);
+    assert!(world.try_get_components::<TestComponent>().is_err());
+}
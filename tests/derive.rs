@@ -0,0 +1,53 @@
+#![cfg(feature = "derive")]
+
+use starry_ecs::World;
+use starry_ecs::component::Component;
+use starry_ecs::resources::Resource;
+
+#[derive(Component)]
+struct Position {
+    x: i32,
+    y: i32
+}
+
+#[derive(Component)]
+enum Shape {
+    Circle(f32),
+    Square { side: f32 },
+    Point
+}
+
+#[derive(Component)]
+struct Tagged<T>(T);
+
+#[derive(Resource)]
+struct Gravity(f32);
+
+#[derive(Resource, Clone)]
+#[starry(skip_clone)]
+struct Settings {
+    volume: u8
+}
+
+#[test]
+fn derived_traits() {
+    let mut world = World::new();
+    world
+        .add_component(Position { x: 1, y: 2 })
+        .add_component(Shape::Circle(1.0))
+        .add_component(Shape::Square { side: 2.0 })
+        .add_component(Shape::Point)
+        .add_component(Tagged("player"))
+        .add_resource(Gravity(9.8))
+        .add_resource(Settings { volume: 5 });
+
+    let snapshot = world.snapshot();
+    world.restore(snapshot);
+
+    assert_eq!(format!("{:?}", *world.get_components::<Position>()[0]), "Position { x: 1, y: 2 }");
+    assert_eq!(format!("{:?}", *world.get_components::<Shape>()[1]), "Square { side: 2.0 }");
+    assert_eq!(format!("{:?}", *world.get_components::<Shape>()[2]), "Point");
+    assert_eq!(format!("{:?}", *world.get_components::<Tagged<&str>>()[0]), "Tagged(\"player\")");
+    assert_eq!(format!("{:?}", *world.get_resource::<Gravity>()), "Gravity(9.8)");
+    assert_eq!(world.get_resource::<Settings>().volume, 5);
+}
@@ -0,0 +1,80 @@
+use starry_ecs::World;
+use starry_ecs::component::Component;
+use starry_ecs::entity::EntityAllocator;
+use starry_ecs::hierarchy::Parent;
+
+#[derive(Clone, Debug)]
+struct Health {
+    points: i32
+}
+impl Component for Health {}
+
+#[test]
+fn allocator_reuses_slots() {
+    let mut allocator = EntityAllocator::new();
+    let first = allocator.allocate();
+    let second = allocator.allocate();
+
+    assert!(allocator.free(first));
+    assert!(!allocator.free(first));
+
+    let reused = allocator.allocate();
+    assert_eq!(reused.index(), first.index());
+    assert_eq!(reused.generation(), first.generation() + 1);
+    assert!(!allocator.is_alive(first));
+    assert!(allocator.is_alive(reused));
+    assert!(allocator.is_alive(second));
+}
+
+#[test]
+fn despawn_stale_handle() {
+    let mut world = World::new();
+    let old = world.spawn().with(Health { points: 1 }).id();
+    world.despawn(old);
+    let new = world.spawn().with(Health { points: 2 }).id();
+
+    world.despawn(old);
+
+    assert_eq!(new.index(), old.index());
+    assert!(world.is_alive(new));
+    assert_eq!(world.get_components::<Health>()[0].points, 2);
+}
+
+#[test]
+fn despawned_children_leave_the_hierarchy() {
+    let mut world = World::new();
+    let root = world.spawn().id();
+    let first = world.spawn().with(Parent::new(root)).id();
+    let second = world.spawn().with(Parent::new(root)).id();
+
+    assert_eq!(world.query_children(root), vec![first, second]);
+
+    world.despawn(first);
+    assert_eq!(world.query_children(root), vec![second]);
+}
+
+#[test]
+fn components_added_to_dead_entities_fail() {
+    let mut world = World::new();
+    let entity = world.spawn().id();
+    world.despawn(entity);
+
+    assert!(world.try_add_component_with_entity(entity, Health { points: 1 }).is_err());
+    assert!(world.try_get_components::<Health>().is_err());
+}
+
+#[test]
+fn despawn_recursive_removes_descendants() {
+    let mut world = World::new();
+    let root = world.spawn().with(Health { points: 1 }).id();
+    let child = world.spawn().with(Parent::new(root)).with(Health { points: 2 }).id();
+    let grandchild = world.spawn().with(Parent::new(child)).id();
+    let other = world.spawn().with(Health { points: 3 }).id();
+
+    world.despawn_recursive(root);
+
+    assert!(!world.is_alive(child) && !world.is_alive(grandchild));
+    assert_eq!(world.entities(), vec![other]);
+    assert_eq!(world.get_components::<Health>().len(), 1);
+    assert!(world.query_children(root).is_empty());
+}
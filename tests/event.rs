@@ -0,0 +1,39 @@
+use starry_ecs::World;
+use starry_ecs::event::Events;
+use starry_ecs::resources::Resource;
+use starry_ecs::systems::DefaultOrdering;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Damage {
+    amount: u32
+}
+
+#[derive(Clone, Debug)]
+struct Health(u32);
+impl Resource for Health {}
+
+fn hit(world: &World) {
+    world.send_event(Damage { amount: 10 });
+}
+
+#[test]
+fn observers_run_before_send_returns() {
+    let mut world = World::new();
+    world
+        .add_resource(Health(100))
+        .add_observer(|world: &World, damage: &Damage| world.get_resource_mut::<Health>().0 -= damage.amount)
+        .add_system(DefaultOrdering::Run, hit)
+        .single_step();
+
+    assert_eq!(world.get_resource::<Health>().0, 90);
+}
+
+#[test]
+fn events_are_kept_once_added() {
+    let mut world = World::new();
+    world.send_event(Damage { amount: 1 });
+    world.add_event::<Damage>().add_system(DefaultOrdering::Run, hit).single_step().single_step();
+
+    assert_eq!(world.get_resource_mut::<Events<Damage>>().drain(), vec![Damage { amount: 10 }, Damage { amount: 10 }]);
+    assert!(world.get_resource::<Events<Damage>>().is_empty());
+}
@@ -27,4 +27,13 @@ pub fn system_2(_: &World) {
 #[test]
 pub fn test_parallization() {
     let _world = World::new().add_system(DefaultOrdering::Run, system_1).add_system(DefaultOrdering::Run, system_2).single_step();
-}
\ No newline at end of file
+}
+fn assert_single_thread(_: &World) {
+    assert_eq!(rayon::current_num_threads(), 1);
+}
+
+#[test]
+pub fn test_custom_thread_pool() {
+    let pool = starry_ecs::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    World::with_thread_pool(pool).add_startup_system(assert_single_thread).add_system(DefaultOrdering::Run, assert_single_thread).start().single_step();
+}
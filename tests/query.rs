@@ -0,0 +1,73 @@
+use starry_ecs::World;
+use starry_ecs::component::Component;
+use starry_ecs::entity::Entity;
+
+#[derive(Clone, Debug)]
+struct Position {
+    x: i32
+}
+impl Component for Position {}
+
+#[derive(Clone, Debug)]
+struct Velocity {
+    x: i32
+}
+impl Component for Velocity {}
+
+#[test]
+fn query_pairs() {
+    let mut world = World::new();
+    let first = world.spawn().with(Position { x: 0 }).with(Velocity { x: 1 }).id();
+    world.spawn().with(Position { x: 10 });
+    let second = world.spawn().with(Velocity { x: 3 }).with(Position { x: 20 }).id();
+    world.add_component(Position { x: 30 }).add_component(Velocity { x: 30 });
+
+    let moved = world
+        .query::<(Entity, &Position, &Velocity)>()
+        .map(|(entity, position, velocity)| (entity, position.x + velocity.x))
+        .collect::<Vec<_>>();
+
+    assert_eq!(moved, vec![(first, 1), (second, 23)]);
+    assert_eq!(world.query::<&Position>().count(), 3);
+}
+
+#[test]
+fn query_mut() {
+    let mut world = World::new();
+    let entity = world.spawn().with(Position { x: 0 }).with(Velocity { x: 2 }).id();
+
+    for (mut position, velocity) in world.query::<(&mut Position, &Velocity)>() {
+        position.x += velocity.x;
+    }
+    assert_eq!(world.get_component_by_entity::<Position>(entity).x, 2);
+}
+
+#[test]
+#[should_panic(expected = "write access")]
+fn query_mut_and_ref_panics() {
+    let mut world = World::new();
+    world.spawn().with(Position { x: 0 });
+
+    let _ = world.query::<(&mut Position, &Position)>();
+}
+
+#[derive(Clone, Debug)]
+struct Static;
+impl Component for Static {}
+
+#[test]
+fn filtered_components() {
+    use starry_ecs::query::{With, Without};
+
+    let mut world = World::new();
+    world.spawn().with(Position { x: 0 }).with(Velocity { x: 1 });
+    world.spawn().with(Position { x: 1 }).with(Velocity { x: 1 }).with(Static);
+    world.spawn().with(Position { x: 2 });
+
+    let moving = world.get_components_filtered::<Position, (With<Velocity>, Without<Static>)>();
+    assert_eq!(moving.len(), 1);
+    assert_eq!(moving[0].x, 0);
+    drop(moving);
+
+    assert!(world.try_get_components_filtered::<Velocity, Without<Position>>().is_err());
+}
@@ -1,8 +1,8 @@
-use starry_ecs::World;
-use starry_ecs::resources::Resource;
+use starry_ecs::{StarryError, World};
+use starry_ecs::resources::{Resource, ResourceAlias};
 use starry_ecs::systems::DefaultOrdering;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TestResource {
     x: i32
 }
@@ -30,4 +30,149 @@ pub fn test_resource(world: &World) {
 #[test]
 pub fn create_resource() {
     let _world = World::new().add_system(DefaultOrdering::Run, test_resource).add_resource(TestResource { x: 100 }).add_resource(RunCounter { runs: 0 }).start().single_step().single_step();
-}
\ No newline at end of file
+}
+#[derive(Debug)]
+struct Window {
+    handle: std::rc::Rc<u32>
+}
+impl starry_ecs::non_send::NonSendResource for Window {}
+
+#[test]
+fn test_non_send_resource() {
+    let mut world = World::new();
+    world.add_non_send_resource(Window { handle: std::rc::Rc::new(1) });
+
+    world.get_non_send_resource_mut::<Window>().handle = std::rc::Rc::new(2);
+    assert_eq!(*world.get_non_send_resource::<Window>().handle, 2);
+}
+
+#[test]
+#[should_panic]
+fn test_non_send_resource_off_main_thread() {
+    let mut world = World::new();
+    world.add_non_send_resource(Window { handle: std::rc::Rc::new(1) });
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            world.get_non_send_resource::<Window>();
+        }).join().unwrap();
+    });
+}
+
+#[test]
+fn non_send_worlds_stay_on_the_main_thread() {
+    let mut world = World::new();
+    world.add_non_send_resource(Window { handle: std::rc::Rc::new(1) });
+
+    std::thread::scope(|scope| {
+        assert!(scope.spawn(|| world.clone()).join().is_err());
+    });
+    assert!(std::thread::spawn(move || drop(world)).join().is_err());
+}
+
+#[test]
+fn non_send_worlds_cant_run_on_another_thread() {
+    let mut world = World::new();
+    world.add_non_send_resource(Window { handle: std::rc::Rc::new(1) });
+
+    assert!(World::run_on_thread(move || world).unwrap().join().is_err());
+}
+
+fn touch_tracked(world: &World) {
+    world.get_resource_mut::<starry_ecs::resources::TrackedResource<TestResource>>().x += 1;
+}
+
+fn count_if_changed(world: &World) {
+    if world.resource_changed::<TestResource>() {
+        world.get_resource_mut::<RunCounter>().runs += 1;
+    }
+}
+
+#[test]
+fn tracked_resources_reset_every_step() {
+    let mut world = World::new();
+    world
+        .add_resource_tracked(TestResource { x: 0 })
+        .add_resource(RunCounter { runs: 0 })
+        .add_system(DefaultOrdering::PostRun, count_if_changed)
+        .single_step();
+    assert_eq!(world.get_resource::<RunCounter>().runs, 0);
+
+    world.add_system(DefaultOrdering::Run, touch_tracked).single_step().single_step();
+    assert_eq!(world.get_resource::<RunCounter>().runs, 2);
+    assert!(!world.resource_changed::<TestResource>());
+}
+
+static NOTIFIED: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(0);
+
+fn remember_x(resource: &TestResource) {
+    NOTIFIED.store(resource.x, std::sync::atomic::Ordering::SeqCst);
+}
+
+#[test]
+fn reactive_resources_notify_on_change() {
+    let mut world = World::new();
+    world.add_reactive_resource(TestResource { x: 0 }).subscribe_resource(remember_x);
+
+    let _ = world.get_reactive_resource_mut::<TestResource>().x;
+    assert_eq!(NOTIFIED.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+    world.get_reactive_resource_mut::<TestResource>().x = 7;
+    assert_eq!(NOTIFIED.load(std::sync::atomic::Ordering::SeqCst), 7);
+}
+
+#[test]
+fn query_resource_chain_missing_resource() {
+    let mut world = World::new();
+    world.add_resource(TestResource { x: 1 });
+
+    let result = world.try_query_resource_chain(|resource: &TestResource, counter: &RunCounter| resource.x as usize + counter.runs);
+    assert!(matches!(result, Err(StarryError::ResourceNotFound(_))));
+
+    world.add_resource(RunCounter { runs: 2 });
+    assert_eq!(world.query_resource_chain(|resource: &TestResource, counter: &RunCounter| resource.x as usize + counter.runs), 3);
+}
+
+#[derive(Clone, Debug)]
+struct Clock {
+    ticks: u32
+}
+impl Resource for Clock {
+    fn tick(&mut self, _delta: std::time::Duration) {
+        self.ticks += 1;
+    }
+}
+
+#[derive(Clone, Debug)]
+#[repr(transparent)]
+struct WallClock(Clock);
+impl Resource for WallClock {}
+unsafe impl ResourceAlias<Clock> for WallClock {}
+
+#[test]
+fn aliased_resources_are_counted_once() {
+    let mut world = World::new();
+    world.add_resource(Clock { ticks: 0 }).add_resource_alias::<Clock, WallClock>();
+
+    world.tick_resources(std::time::Duration::from_millis(16));
+    assert_eq!(world.get_resource::<WallClock>().0.ticks, 1);
+    assert_eq!(world.total_memory_estimate(), std::mem::size_of::<Clock>());
+}
+
+#[test]
+fn tracked_resources_forward_ticks() {
+    let mut world = World::new();
+    world.add_resource_tracked(Clock { ticks: 0 });
+
+    world.tick_resources(std::time::Duration::from_millis(16));
+    assert_eq!(world.get_resource::<starry_ecs::resources::TrackedResource<Clock>>().ticks, 1);
+}
+
+#[test]
+fn reactive_resources_forward_ticks() {
+    let mut world = World::new();
+    world.add_reactive_resource(Clock { ticks: 0 });
+
+    world.tick_resources(std::time::Duration::from_millis(16));
+    assert_eq!(world.get_resource::<starry_ecs::resources::ReactiveResource<Clock>>().ticks, 1);
+}
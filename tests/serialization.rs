@@ -0,0 +1,280 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use starry_ecs::World;
+use starry_ecs::component::Component;
+use starry_ecs::resources::{Resource, ResourceAlias};
+use starry_ecs::serialization::Serializable;
+#[cfg(feature = "bincode")]
+use starry_ecs::binary::{BinaryComponent, BinaryResource};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Position {
+    x: i32,
+    y: i32
+}
+impl Component for Position {}
+impl Serializable for Position {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Score {
+    points: u32
+}
+impl Resource for Score {}
+impl Serializable for Score {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[repr(transparent)]
+struct HighScore(Score);
+impl Resource for HighScore {}
+unsafe impl ResourceAlias<Score> for HighScore {}
+
+#[cfg(feature = "bincode")]
+impl BinaryComponent for Position {}
+#[cfg(feature = "bincode")]
+impl BinaryResource for Score {}
+
+#[cfg(feature = "bincode")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Velocity {
+    dx: i32
+}
+#[cfg(feature = "bincode")]
+impl Component for Velocity {}
+#[cfg(feature = "bincode")]
+impl BinaryComponent for Velocity {}
+
+#[test]
+fn component_round_trip() {
+    let mut world = World::new();
+    world.add_component(Position { x: 1, y: 2 }).add_component(Position { x: 3, y: 4 });
+
+    let serialized = world.serialize_components::<Position>().unwrap();
+    assert_eq!(serialized, vec![r#"{"x":1,"y":2}"#, r#"{"x":3,"y":4}"#]);
+
+    let mut loaded = World::new();
+    loaded.deserialize_components::<Position>(&serialized).unwrap();
+    assert_eq!(loaded.get_components::<Position>()[1].y, 4);
+}
+
+#[test]
+fn world_round_trip() {
+    let mut world = World::new();
+    world
+        .register_serializable_component::<Position>()
+        .register_serializable_resource::<Score>()
+        .add_component(Position { x: 1, y: 2 })
+        .add_resource(Score { points: 10 });
+
+    let json = world.to_json().unwrap();
+
+    let mut loaded = World::new();
+    loaded
+        .register_serializable_component::<Position>()
+        .register_serializable_resource::<Score>()
+        .add_component(Position { x: 0, y: 0 })
+        .from_json(&json)
+        .unwrap();
+
+    let positions = loaded.get_components::<Position>();
+    assert_eq!(positions.len(), 1);
+    assert_eq!((positions[0].x, positions[0].y), (1, 2));
+    assert_eq!(loaded.get_resource::<Score>().points, 10);
+}
+
+#[test]
+fn loaded_resources_keep_their_aliases() {
+    let mut world = World::new();
+    world.register_serializable_resource::<Score>().add_resource(Score { points: 10 });
+
+    let mut loaded = World::new();
+    loaded
+        .register_serializable_resource::<Score>()
+        .add_resource(Score { points: 0 })
+        .add_resource_alias::<Score, HighScore>()
+        .from_json(&world.to_json().unwrap())
+        .unwrap();
+
+    assert_eq!(loaded.get_resource::<HighScore>().0.points, 10);
+    loaded.get_resource_mut::<HighScore>().0.points = 20;
+    assert_eq!(loaded.get_resource::<Score>().points, 20);
+}
+
+#[derive(Clone, Debug)]
+struct Hooks {
+    added: usize,
+    removed: usize
+}
+impl Resource for Hooks {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Hooked;
+impl Component for Hooked {
+    fn on_add(&mut self, world: &World) {
+        if let Ok(mut hooks) = world.try_get_resource_mut::<Hooks>() {
+            hooks.added += 1;
+        }
+    }
+
+    fn on_remove(&mut self, world: &World) {
+        world.get_resource_mut::<Hooks>().removed += 1;
+    }
+}
+impl Serializable for Hooked {}
+#[cfg(feature = "bincode")]
+impl BinaryComponent for Hooked {}
+
+#[test]
+fn loading_calls_hooks() {
+    let mut world = World::new();
+    world.register_serializable_component::<Hooked>().add_component(Hooked);
+
+    let mut loaded = World::new();
+    loaded
+        .register_serializable_component::<Hooked>()
+        .add_resource(Hooks { added: 0, removed: 0 })
+        .add_component(Hooked)
+        .from_json(&world.to_json().unwrap())
+        .unwrap();
+
+    let hooks = loaded.get_resource::<Hooks>();
+    assert_eq!((hooks.added, hooks.removed), (2, 1));
+}
+
+#[test]
+fn entities_round_trip() {
+    let mut world = World::new();
+    world.register_serializable_component::<Position>();
+    let entity = world.spawn().with(Position { x: 5, y: 6 }).id();
+
+    let mut loaded = World::new();
+    loaded.register_serializable_component::<Position>().from_json(&world.to_json().unwrap()).unwrap();
+
+    let (loaded_entity, position) = loaded.query::<(starry_ecs::entity::Entity, &Position)>().next().unwrap();
+    assert_eq!(loaded_entity, entity);
+    assert_eq!(position.x, 5);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn binary_round_trip() {
+    let mut world = World::new();
+    world
+        .register_binary_component::<Position>()
+        .register_binary_resource::<Score>()
+        .add_resource(Score { points: 10 });
+    let entity = world.spawn().with(Position { x: 1, y: 2 }).id();
+
+    let mut save = vec![];
+    world.serialize_state(&mut save).unwrap();
+
+    let mut loaded = World::new();
+    loaded
+        .register_binary_component::<Position>()
+        .register_binary_resource::<Score>()
+        .add_component(Position { x: 0, y: 0 })
+        .deserialize_state(save.as_slice())
+        .unwrap();
+
+    let (loaded_entity, position) = loaded.query::<(starry_ecs::entity::Entity, &Position)>().next().unwrap();
+    assert_eq!(loaded_entity, entity);
+    assert_eq!((position.x, position.y), (1, 2));
+    assert_eq!(loaded.get_components::<Position>().len(), 1);
+    assert_eq!(loaded.get_resource::<Score>().points, 10);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn binary_skips_unknown_types() {
+    let mut world = World::new();
+    world
+        .register_binary_component::<Velocity>()
+        .register_binary_component::<Position>()
+        .register_binary_resource::<Score>()
+        .add_component(Velocity { dx: 3 })
+        .add_component(Position { x: 1, y: 2 })
+        .add_resource(Score { points: 10 });
+
+    let mut save = vec![];
+    world.serialize_state(&mut save).unwrap();
+
+    let mut loaded = World::new();
+    loaded.register_binary_component::<Position>().deserialize_state(save.as_slice()).unwrap();
+
+    assert_eq!(loaded.get_components::<Position>()[0].y, 2);
+    assert!(loaded.try_get_components::<Velocity>().is_err());
+    assert!(loaded.try_get_resource::<Score>().is_err());
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn binary_output_is_deterministic() {
+    let mut first = World::new();
+    first
+        .register_binary_component::<Position>()
+        .register_binary_component::<Velocity>()
+        .register_binary_resource::<Score>();
+    let mut second = World::new();
+    second
+        .register_binary_resource::<Score>()
+        .register_binary_component::<Velocity>()
+        .register_binary_component::<Position>();
+
+    for world in [&mut first, &mut second] {
+        world.add_component(Position { x: 1, y: 2 }).add_component(Velocity { dx: 3 }).add_resource(Score { points: 10 });
+    }
+
+    let (mut first_save, mut second_save) = (vec![], vec![]);
+    first.serialize_state(&mut first_save).unwrap();
+    second.serialize_state(&mut second_save).unwrap();
+    assert_eq!(first_save, second_save);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn binary_rejects_malformed_documents() {
+    let mut world = World::new();
+    world.register_binary_component::<Position>().add_component(Position { x: 1, y: 2 });
+    let mut save = vec![];
+    world.serialize_state(&mut save).unwrap();
+
+    let mut loaded = World::new();
+    loaded.register_binary_component::<Position>();
+
+    let mut bad_magic = save.clone();
+    bad_magic[0] = b'X';
+    assert!(loaded.deserialize_state(bad_magic.as_slice()).is_err());
+
+    let mut bad_version = save.clone();
+    bad_version[4] = 99;
+    assert!(loaded.deserialize_state(bad_version.as_slice()).is_err());
+
+    assert!(loaded.deserialize_state(&save[..save.len() - 1]).is_err());
+
+    // The length of the first type name, claiming far more bytes than the document holds
+    let mut huge_length = save.clone();
+    huge_length[17..25].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(loaded.deserialize_state(huge_length.as_slice()).is_err());
+
+    assert!(loaded.try_get_components::<Position>().is_err());
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn binary_loading_calls_hooks() {
+    let mut world = World::new();
+    world.register_binary_component::<Hooked>().add_component(Hooked);
+    let mut save = vec![];
+    world.serialize_state(&mut save).unwrap();
+
+    let mut loaded = World::new();
+    loaded
+        .register_binary_component::<Hooked>()
+        .add_resource(Hooks { added: 0, removed: 0 })
+        .add_component(Hooked)
+        .deserialize_state(save.as_slice())
+        .unwrap();
+
+    let hooks = loaded.get_resource::<Hooks>();
+    assert_eq!((hooks.added, hooks.removed), (2, 1));
+}
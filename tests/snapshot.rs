@@ -0,0 +1,204 @@
+use starry_ecs::World;
+use starry_ecs::snapshot::{MergeStrategy, WorldDiff};
+use starry_ecs::component::Component;
+use starry_ecs::resources::{Resource, ResourceAlias};
+use starry_ecs::systems::DefaultOrdering;
+
+#[derive(Clone, Debug)]
+struct Position {
+    x: i32
+}
+impl Component for Position {}
+
+fn move_right(world: &World) {
+    for mut position in world.get_components_mut::<Position>() {
+        position.x += 1;
+    }
+}
+
+#[test]
+fn restore_snapshot() {
+    let mut world = World::new();
+    world.add_component(Position { x: 0 }).add_system(DefaultOrdering::Run, move_right).single_step();
+
+    let snapshot = world.snapshot();
+    assert_eq!(snapshot.frame(), 1);
+
+    world.single_step().single_step();
+    assert_eq!(world.get_components::<Position>()[0].x, 3);
+
+    world.restore(snapshot);
+    assert_eq!(world.get_components::<Position>()[0].x, 1);
+    assert_eq!(world.frame(), 1);
+}
+
+#[test]
+fn merge_remaps_entities() {
+    let mut world = World::new();
+    let existing = world.spawn().with(Position { x: 0 }).id();
+
+    let mut chunk = World::new();
+    let parent = chunk.spawn().with(Position { x: 10 }).id();
+    let child = chunk.spawn().with(Position { x: 11 }).id();
+    chunk.set_parent(child, parent);
+
+    world.merge(chunk, MergeStrategy::Error);
+
+    let entities = world.entities();
+    assert_eq!(entities.len(), 3);
+    let merged_parent = *entities.iter().find(|e| **e != existing && world.get_component_by_entity::<Position>(**e).x == 10).unwrap();
+    let children = world.query_children(merged_parent);
+    assert_eq!(children.len(), 1);
+    assert_eq!(world.get_component_by_entity::<Position>(children[0]).x, 11);
+}
+
+#[derive(Clone, Debug)]
+struct WindowSize {
+    width: u32
+}
+impl Resource for WindowSize {}
+
+#[derive(Clone, Debug)]
+#[repr(transparent)]
+struct ScreenDimensions(WindowSize);
+impl Resource for ScreenDimensions {}
+unsafe impl ResourceAlias<WindowSize> for ScreenDimensions {}
+
+#[test]
+fn aliases_stay_shared_in_copies() {
+    let mut world = World::new();
+    world.add_resource(WindowSize { width: 800 }).add_resource_alias::<WindowSize, ScreenDimensions>();
+
+    let copy = world.clone_without_systems();
+    copy.get_resource_mut::<WindowSize>().width = 1024;
+    assert_eq!(copy.get_resource::<ScreenDimensions>().0.width, 1024);
+    assert_eq!(world.get_resource::<ScreenDimensions>().0.width, 800);
+
+    let snapshot = world.snapshot();
+    world.get_resource_mut::<WindowSize>().width = 640;
+    world.restore(snapshot);
+    world.get_resource_mut::<ScreenDimensions>().0.width = 1280;
+    assert_eq!(world.get_resource::<WindowSize>().width, 1280);
+}
+
+#[test]
+#[should_panic(expected = "twice")]
+fn alias_pair_is_locked_once() {
+    let mut world = World::new();
+    world.add_resource(WindowSize { width: 800 }).add_resource_alias::<WindowSize, ScreenDimensions>();
+
+    let _ = world.resource_mut_pair::<WindowSize, ScreenDimensions>();
+}
+
+#[derive(Clone, Debug)]
+struct Ping;
+
+#[test]
+fn clone_without_systems_drops_system_state() {
+    let mut world = World::new();
+    world.add_component(Position { x: 0 }).add_observer(|world: &World, _: &Ping| move_right(world));
+    *world.get_local::<u32>(move_right) = 5;
+
+    let copy = world.clone_without_systems();
+    *copy.get_local::<u32>(move_right) += 1;
+    copy.send_event(Ping);
+
+    assert_eq!(*world.get_local::<u32>(move_right), 5);
+    assert_eq!(*copy.get_local::<u32>(move_right), 1);
+    assert_eq!(copy.get_components::<Position>()[0].x, 0);
+}
+
+#[test]
+fn forks_have_their_own_locals() {
+    let world = World::new();
+    *world.get_local::<u32>(move_right) = 5;
+
+    let fork = world.fork();
+    *fork.get_local::<u32>(move_right) += 1;
+
+    assert_eq!(*world.get_local::<u32>(move_right), 5);
+    assert_eq!(*fork.get_local::<u32>(move_right), 1);
+}
+
+#[derive(Clone, Debug)]
+struct Hooks {
+    added: usize,
+    removed: usize
+}
+impl Resource for Hooks {}
+
+#[derive(Clone, Debug)]
+struct Hooked;
+impl Component for Hooked {
+    fn on_add(&mut self, world: &World) {
+        world.get_resource_mut::<Hooks>().added += 1;
+    }
+
+    fn on_remove(&mut self, world: &World) {
+        world.get_resource_mut::<Hooks>().removed += 1;
+    }
+}
+
+#[test]
+fn merged_components_call_hooks() {
+    let mut world = World::new();
+    world.add_resource(Hooks { added: 0, removed: 0 }).add_component(Hooked);
+
+    let fork = world.fork();
+    world.merge_components_from::<Hooked>(&fork);
+
+    let hooks = world.get_resource::<Hooks>();
+    assert_eq!((hooks.added, hooks.removed), (2, 1));
+}
+
+#[test]
+fn merged_worlds_call_add_hooks() {
+    let mut world = World::new();
+    world.add_resource(Hooks { added: 0, removed: 0 });
+
+    let mut chunk = World::new();
+    chunk.add_resource(Hooks { added: 0, removed: 0 }).spawn().with(Hooked);
+    world.merge(chunk, MergeStrategy::KeepSelf);
+
+    assert_eq!(world.get_resource::<Hooks>().added, 1);
+}
+
+#[derive(Clone, PartialEq)]
+struct Secret {
+    visible: i32,
+    hidden: i32
+}
+impl Component for Secret {}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secret").field("visible", &self.visible).finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Added(usize);
+impl Resource for Added {}
+
+fn count_added(_: &Secret, world: &World) {
+    world.get_resource_mut::<Added>().0 += 1;
+}
+
+#[test]
+fn diffs_compare_with_partial_eq_and_call_hooks() {
+    let mut world = World::new();
+    world.register_diffable_component::<Secret>().add_component(Secret { visible: 0, hidden: 0 }).add_component(Secret { visible: 1, hidden: 0 });
+    let mut replica = world.fork();
+    replica.add_resource(Added(0)).with_component_hook(count_added, |_, _| {});
+
+    let before = world.snapshot();
+    assert!(WorldDiff::compute(&before, &world.snapshot()).is_empty());
+
+    world.get_components_mut::<Secret>()[0].hidden = 7;
+    let diff = WorldDiff::compute(&before, &world.snapshot());
+    assert_eq!((diff.removed_component_count(), diff.added_component_count()), (1, 1));
+
+    replica.apply_diff(diff);
+    assert_eq!(replica.get_resource::<Added>().0, 1);
+    assert_eq!(replica.iter_components::<Secret>().map(|s| s.hidden).sum::<i32>(), 7);
+}
@@ -0,0 +1,97 @@
+use starry_ecs::World;
+use starry_ecs::resources::{FrameCount, Resource};
+use starry_ecs::systems::DefaultOrdering;
+
+#[derive(Clone, Debug)]
+struct StartupCounter {
+    runs: usize
+}
+impl Resource for StartupCounter {}
+
+fn count_startup(world: &World) {
+    world.get_resource_mut::<StartupCounter>().runs += 1;
+}
+
+#[test]
+fn start_runs_once() {
+    let mut world = World::new();
+    world.add_resource(StartupCounter { runs: 0 }).add_startup_system(count_startup).start().start();
+
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 1);
+}
+
+#[test]
+fn single_step_skips_startup() {
+    let mut world = World::new();
+    world.add_resource(StartupCounter { runs: 0 }).add_startup_system(count_startup).add_system(DefaultOrdering::Run, |_| {}).single_step();
+
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 0);
+}
+
+#[test]
+fn start_adds_frame_count() {
+    let mut world = World::new();
+    world.start().single_step().single_step();
+    assert_eq!(world.get_resource::<FrameCount>().total, 2);
+
+    world.clear_systems().single_step();
+    assert_eq!(*world.get_resource::<FrameCount>(), FrameCount { total: 3, since_start: 1 });
+}
+
+#[test]
+fn startup_conditions_skip_systems() {
+    let mut world = World::new();
+    world
+        .add_resource(StartupCounter { runs: 0 })
+        .add_startup_system_with_condition(count_startup, || false)
+        .start();
+
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 0);
+}
+
+#[test]
+fn removed_startup_systems_dont_run() {
+    let mut world = World::new();
+    world.add_resource(StartupCounter { runs: 0 }).add_startup_system(count_startup).add_startup_system(count_startup);
+
+    assert!(world.remove_startup_system(count_startup));
+    world.start();
+
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 1);
+}
+
+#[test]
+fn after_startup_systems_run_once() {
+    let mut world = World::new();
+    world.add_resource(StartupCounter { runs: 0 }).add_system_after_startup(count_startup).single_step();
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 0);
+
+    world.start();
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 0);
+
+    world.single_step().single_step();
+    assert_eq!(world.get_resource::<StartupCounter>().runs, 1);
+}
+
+#[derive(Clone, Debug)]
+struct Threads {
+    ids: Vec<std::thread::ThreadId>
+}
+impl Resource for Threads {}
+
+fn record_thread(world: &World) {
+    world.get_resource_mut::<Threads>().ids.push(std::thread::current().id());
+}
+
+#[test]
+fn after_startup_systems_follow_sequential_steps() {
+    let mut world = World::new();
+    world
+        .add_resource(Threads { ids: vec![] })
+        .add_system_after_startup(record_thread)
+        .add_system_after_startup(record_thread)
+        .start()
+        .run_systems_sequentially();
+
+    assert_eq!(world.get_resource::<Threads>().ids, vec![std::thread::current().id(); 2]);
+}
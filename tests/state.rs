@@ -0,0 +1,80 @@
+use starry_ecs::World;
+use starry_ecs::resources::Resource;
+use starry_ecs::state::{StateEvent, StateResource};
+use starry_ecs::systems::DefaultOrdering;
+
+#[derive(Clone, Debug, PartialEq)]
+enum GameState {
+    MainMenu,
+    InGame,
+    Paused
+}
+
+#[derive(Clone, Debug)]
+struct Trace {
+    events: Vec<&'static str>
+}
+impl Resource for Trace {}
+
+fn exit_menu(world: &World) {
+    world.get_resource_mut::<Trace>().events.push("exit menu");
+}
+
+fn enter_game(world: &World) {
+    world.get_resource_mut::<Trace>().events.push("enter game");
+}
+
+fn enter_paused(world: &World) {
+    world.get_resource_mut::<Trace>().events.push("enter paused");
+}
+
+fn pause(world: &World) {
+    world.transition_to(GameState::Paused);
+}
+
+#[test]
+fn transitions_run_exit_then_enter() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { events: vec![] })
+        .add_state(GameState::MainMenu)
+        .add_state_system(GameState::MainMenu, StateEvent::OnExit, exit_menu)
+        .add_state_system(GameState::InGame, StateEvent::OnEnter, enter_game)
+        .add_state_system(GameState::Paused, StateEvent::OnEnter, enter_paused);
+
+    world.single_step();
+    assert!(world.get_resource::<Trace>().events.is_empty());
+
+    world.transition_to(GameState::InGame);
+    world.single_step();
+    assert_eq!(world.get_resource::<Trace>().events, vec!["exit menu", "enter game"]);
+}
+
+#[test]
+fn transitions_queued_by_systems_apply_next_step() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { events: vec![] })
+        .add_state(GameState::InGame)
+        .add_state_system(GameState::Paused, StateEvent::OnEnter, enter_paused)
+        .add_system(DefaultOrdering::Run, pause)
+        .single_step();
+
+    assert_eq!(world.get_resource::<StateResource<GameState>>().next(), Some(&GameState::Paused));
+    world.single_step();
+    assert_eq!(world.get_resource::<StateResource<GameState>>().current(), &GameState::Paused);
+    assert_eq!(world.get_resource::<Trace>().events, vec!["enter paused"]);
+}
+
+#[test]
+fn removed_state_machines_are_skipped() {
+    let mut world = World::new();
+    let before = world.snapshot();
+    world.add_state(GameState::MainMenu).start();
+
+    world.take_resource::<StateResource<GameState>>().unwrap();
+    world.single_step();
+
+    world.add_state(GameState::MainMenu).restore(before);
+    world.single_step();
+}
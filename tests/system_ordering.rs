@@ -1,4 +1,8 @@
-use starry_ecs::{systems::{DefaultOrdering, SystemOrdering}, World};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use starry_ecs::schedule::Schedule;
+use starry_ecs::systems::SystemMeta;
+use starry_ecs::{resources::Resource, systems::{DefaultOrdering, SystemOrdering}, StarryError, SystemType, World};
 
 pub fn first(_: &World) {
     println!("First");
@@ -23,9 +27,9 @@ pub enum CustomOrdering {
 
 impl SystemOrdering for CustomOrdering {}
 
-impl Into<i32> for CustomOrdering {
-    fn into(self) -> i32 {
-        self as i32
+impl From<CustomOrdering> for i32 {
+    fn from(ordering: CustomOrdering) -> i32 {
+        ordering as i32
     }
 }
 
@@ -33,3 +37,291 @@ impl Into<i32> for CustomOrdering {
 pub fn test_custom_order() {
     World::new().add_system(CustomOrdering::CPreRun, first).add_system(CustomOrdering::CRun, second).single_step().single_step();
 }
+
+#[derive(Clone, Debug)]
+struct Trace {
+    order: Vec<&'static str>
+}
+impl Resource for Trace {}
+
+fn trace_a(world: &World) {
+    world.get_resource_mut::<Trace>().order.push("a");
+}
+
+fn trace_b(world: &World) {
+    world.get_resource_mut::<Trace>().order.push("b");
+}
+
+fn trace_c(world: &World) {
+    world.get_resource_mut::<Trace>().order.push("c");
+}
+
+#[derive(Copy, Clone)]
+pub enum DependentOrdering {
+    Independent,
+    AfterB,
+    AfterC,
+    AfterA
+}
+
+impl SystemOrdering for DependentOrdering {
+    fn dependencies(&self) -> Vec<SystemType> {
+        match self {
+            DependentOrdering::Independent => vec![],
+            DependentOrdering::AfterB => vec![trace_b],
+            DependentOrdering::AfterC => vec![trace_c],
+            DependentOrdering::AfterA => vec![trace_a]
+        }
+    }
+}
+
+impl From<DependentOrdering> for i32 {
+    fn from(_: DependentOrdering) -> i32 {
+        0
+    }
+}
+
+#[test]
+pub fn test_dependencies() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DependentOrdering::AfterB, trace_a)
+        .add_system(DependentOrdering::AfterC, trace_b)
+        .add_system(DependentOrdering::Independent, trace_c)
+        .single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["c", "b", "a"]);
+}
+
+#[test]
+pub fn test_cyclic_dependencies() {
+    let result = World::new()
+        .add_resource(Trace { order: vec![] })
+        .add_system(DependentOrdering::AfterB, trace_a)
+        .add_system(DependentOrdering::AfterA, trace_b)
+        .try_single_step()
+        .map(|_| ());
+
+    assert!(matches!(result, Err(StarryError::CyclicDependency(0))));
+}
+
+fn exclusive_trace(world: &mut World) {
+    world.get_resource_mut::<Trace>().order.push("exclusive");
+}
+
+#[test]
+pub fn test_exclusive_systems() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DefaultOrdering::PostRun, trace_b)
+        .add_exclusive_system(DefaultOrdering::Run, exclusive_trace)
+        .add_system(DefaultOrdering::Run, trace_a)
+        .single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["a", "exclusive", "b"]);
+}
+
+fn exclusive_clear(world: &mut World) {
+    world.get_resource_mut::<Trace>().order.clear();
+}
+
+#[test]
+pub fn test_exclusive_system_names() {
+    let mut world = World::new();
+    world
+        .add_exclusive_system(DefaultOrdering::Run, exclusive_trace)
+        .add_exclusive_system(DefaultOrdering::Run, exclusive_clear);
+
+    let order = world.system_execution_order();
+    let names = order.lines().filter(|line| line.ends_with("exclusively")).collect::<Vec<_>>();
+    assert_eq!(names.len(), 2);
+    assert_ne!(names[0], names[1]);
+}
+
+fn panicking(_: &World) {
+    panic!("System failure");
+}
+
+fn record_panic(system: SystemType, payload: Box<dyn std::any::Any + Send>) {
+    assert!(std::ptr::fn_addr_eq(system, panicking as SystemType));
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"System failure"));
+}
+
+#[test]
+pub fn test_error_handler() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DefaultOrdering::Run, panicking)
+        .add_system(DefaultOrdering::PostRun, trace_a)
+        .single_step_with_error_handler(record_panic);
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["a"]);
+}
+
+static RECOVERED_PANICS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_panic(err: StarryError) {
+    assert!(matches!(err, StarryError::SystemPanic(ref message) if message == "System failure"));
+    RECOVERED_PANICS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+pub fn test_single_step_recovering() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DefaultOrdering::Run, panicking)
+        .add_system(DefaultOrdering::PostRun, trace_a)
+        .set_error_callback(count_panic);
+
+    for _ in 0..3 {
+        world.single_step_recovering();
+    }
+
+    assert_eq!(RECOVERED_PANICS.load(Ordering::SeqCst), 3);
+    assert_eq!(world.get_resource::<Trace>().order, vec!["a", "a", "a"]);
+}
+
+#[test]
+pub fn test_before_and_after() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DefaultOrdering::Run, trace_b)
+        .add_system_after(DefaultOrdering::Run, trace_b, trace_c)
+        .add_system_before(DefaultOrdering::Run, trace_b, trace_a)
+        .single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["a", "b", "c"]);
+}
+
+#[test]
+pub fn test_set_and_run_schedule() {
+    let mut editor = Schedule::new();
+    editor.add_system(DefaultOrdering::Run, trace_b);
+
+    let mut world = World::new();
+    world.add_resource(Trace { order: vec![] }).add_system(DefaultOrdering::Run, trace_a);
+
+    let game = world.set_schedule(editor);
+    world.single_step().run_schedule(&game).single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["b", "a", "b"]);
+    assert_eq!(world.frame(), 3);
+    assert_eq!(game.system_count(), 1);
+}
+
+#[test]
+pub fn test_run_systems_sequentially() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DefaultOrdering::Run, trace_c)
+        .add_system(DefaultOrdering::Run, trace_a)
+        .add_system(DefaultOrdering::Run, trace_b)
+        .run_systems_sequentially();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["c", "a", "b"]);
+}
+
+#[test]
+pub fn test_validate_no_conflicts() {
+    let mut world = World::new();
+    world
+        .add_system(DefaultOrdering::Run, trace_a)
+        .add_system(DefaultOrdering::Run, trace_b)
+        .add_system_after(DefaultOrdering::Run, trace_a, trace_c)
+        .describe_system(trace_a, SystemMeta::new("trace_a").writes::<Trace>())
+        .describe_system(trace_b, SystemMeta::new("trace_b").reads::<Trace>())
+        .describe_system(trace_c, SystemMeta::new("trace_c").writes::<Trace>());
+
+    // trace_c waits for trace_a, so only trace_a and trace_b run in parallel
+    let conflicts = world.validate_no_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert!(conflicts[0].starts_with("System trace_a writes to") && conflicts[0].ends_with("while trace_b reads it"));
+}
+
+#[test]
+pub fn test_every_n_frames() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system_every_n_frames(DefaultOrdering::Run, 3, trace_a)
+        .add_system_every_n_frames_with_offset(DefaultOrdering::Run, 3, 1, trace_b);
+
+    for _ in 0..6 {
+        world.run_systems_sequentially();
+    }
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["a", "b", "a", "b"]);
+}
+
+#[test]
+pub fn test_every_n_frames_per_registration() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system_every_n_frames(DefaultOrdering::Run, 2, trace_a)
+        .add_system_every_n_frames(DefaultOrdering::Run, 3, trace_a);
+
+    for _ in 0..6 {
+        world.run_systems_sequentially();
+    }
+    assert_eq!(world.get_resource::<Trace>().order.len(), 5);
+
+    world.get_resource_mut::<Trace>().order.clear();
+    world.get_resource_mut::<starry_ecs::resources::FrameCount>().total = 6;
+    world.run_systems_sequentially();
+    assert_eq!(world.get_resource::<Trace>().order.len(), 2);
+    world.run_systems_sequentially();
+    assert_eq!(world.get_resource::<Trace>().order.len(), 2);
+}
+
+#[test]
+pub fn test_system_group_sorted() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system_group_sorted(2, vec![trace_c, trace_b, trace_a])
+        .single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["c", "b", "a"]);
+}
+
+#[test]
+pub fn test_remove_system_forgets_dependencies() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system(DependentOrdering::AfterB, trace_a)
+        .add_system(DependentOrdering::AfterA, trace_b)
+        .describe_system(trace_b, SystemMeta::new("trace_b").writes::<Trace>());
+
+    assert!(world.remove_system(DependentOrdering::AfterA, trace_b));
+    world.add_system(DependentOrdering::Independent, trace_b).single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["b", "a"]);
+    assert!(!world.system_execution_order().contains("trace_b"));
+}
+
+#[derive(Debug, Clone)]
+struct Gate;
+impl Resource for Gate {}
+
+#[test]
+pub fn test_remove_and_readd_conditional_system() {
+    let mut world = World::new();
+    world
+        .add_resource(Trace { order: vec![] })
+        .add_system_conditional_on_resource::<Gate, _>(DefaultOrdering::Run, trace_a)
+        .single_step();
+    assert!(world.get_resource::<Trace>().order.is_empty());
+
+    assert!(world.remove_system(DefaultOrdering::Run, trace_a));
+    world.add_system(DefaultOrdering::Run, trace_a).single_step();
+
+    assert_eq!(world.get_resource::<Trace>().order, vec!["a"]);
+}
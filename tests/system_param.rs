@@ -0,0 +1,129 @@
+use starry_ecs::World;
+use starry_ecs::component::Component;
+use starry_ecs::query::QueryResult;
+use starry_ecs::resources::{Resource, ResourceAlias};
+use starry_ecs::system_param::{Res, ResMut};
+use starry_ecs::systems::{DefaultOrdering, SystemMeta};
+
+#[derive(Clone, Debug)]
+struct Position {
+    x: i32
+}
+impl Component for Position {}
+
+#[derive(Clone, Debug)]
+struct Total(i32);
+impl Resource for Total {}
+
+fn sum_positions(positions: QueryResult<&Position>, mut total: ResMut<Total>) {
+    total.0 = positions.map(|p| p.x).sum();
+}
+
+fn read_total(total: Res<Total>, _: Res<Total>) {
+    assert_eq!(total.0, 3);
+}
+
+fn write_twice(_: ResMut<Total>, _: ResMut<Total>) {}
+
+fn read_and_write(_: Res<Total>, _: ResMut<Total>) {}
+
+#[derive(Clone, Debug)]
+#[repr(transparent)]
+struct Score(Total);
+impl Resource for Score {}
+unsafe impl ResourceAlias<Total> for Score {}
+
+fn write_through_alias(_: ResMut<Total>, _: ResMut<Score>) {}
+
+#[test]
+fn params_are_fetched() {
+    let mut world = World::new();
+    world.add_resource(Total(0));
+    world.spawn().with(Position { x: 1 });
+    world.spawn().with(Position { x: 2 });
+
+    world.add_param_system(DefaultOrdering::Run, sum_positions);
+    world.add_param_system(DefaultOrdering::PostRun, read_total);
+    world.single_step();
+
+    assert_eq!(world.get_resource::<Total>().0, 3);
+}
+
+#[derive(Clone, Debug)]
+struct Velocity {
+    x: i32
+}
+impl Component for Velocity {}
+
+fn movement(positions: QueryResult<(&mut Position, &Velocity)>) {
+    for (mut position, velocity) in positions {
+        position.x += velocity.x;
+    }
+}
+
+fn move_and_read(_: QueryResult<&mut Position>, _: QueryResult<&Position>) {}
+
+#[test]
+fn queries_can_write() {
+    let mut world = World::new();
+    let entity = world.spawn().with(Position { x: 1 }).with(Velocity { x: 2 }).id();
+
+    world.add_param_system(DefaultOrdering::Run, movement);
+    world.single_step();
+
+    assert_eq!(world.get_component_by_entity::<Position>(entity).x, 3);
+}
+
+#[test]
+#[should_panic(expected = "write access")]
+fn writing_and_reading_a_component_panics() {
+    World::new().add_param_system(DefaultOrdering::Run, move_and_read);
+}
+
+#[test]
+#[should_panic(expected = "write access")]
+fn writing_twice_panics() {
+    World::new().add_param_system(DefaultOrdering::Run, write_twice);
+}
+
+#[test]
+#[should_panic(expected = "write access")]
+fn reading_and_writing_panics() {
+    World::new().add_param_system(DefaultOrdering::Run, read_and_write);
+}
+
+#[test]
+#[should_panic(expected = "write access")]
+fn writing_through_an_alias_panics() {
+    World::new().add_resource(Total(0)).add_resource_alias::<Total, Score>().add_param_system(DefaultOrdering::Run, write_through_alias);
+}
+
+#[test]
+fn param_systems_are_described() {
+    let mut world = World::new();
+    world.add_param_system(DefaultOrdering::Run, sum_positions);
+    world.add_param_system(DefaultOrdering::Run, read_total);
+
+    assert_eq!(world.validate_no_conflicts().len(), 1);
+}
+
+fn count_positions(positions: QueryResult<&Position>) {
+    assert_eq!(positions.count(), 0);
+}
+
+fn move_positions(world: &World) {
+    for mut position in world.get_components_mut::<Position>() {
+        position.x += 1;
+    }
+}
+
+#[test]
+fn queries_declare_component_reads() {
+    let mut world = World::new();
+    world.add_param_system(DefaultOrdering::Run, count_positions);
+    world
+        .add_system(DefaultOrdering::Run, move_positions)
+        .describe_system(move_positions, SystemMeta::new("move_positions").writes::<Position>());
+
+    assert_eq!(world.validate_no_conflicts().len(), 1);
+}
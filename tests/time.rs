@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use starry_ecs::World;
+use starry_ecs::time::{advance_timers_system, DeltaTime, Timer};
+
+#[test]
+fn timers_finish_once_unless_repeating() {
+    let mut once = Timer::new(Duration::from_millis(100), false);
+    once.tick(Duration::from_millis(60));
+    assert!(!once.finished);
+    once.tick(Duration::from_millis(60));
+    assert!(once.finished);
+    assert_eq!(once.elapsed, Duration::from_millis(100));
+
+    let mut repeating = Timer::new(Duration::from_millis(100), true);
+    repeating.tick(Duration::from_millis(150));
+    assert!(repeating.finished);
+    assert_eq!(repeating.elapsed, Duration::from_millis(50));
+    repeating.tick(Duration::from_millis(10));
+    assert!(!repeating.finished);
+}
+
+#[test]
+fn timer_components_advance_with_delta_time() {
+    let mut world = World::new();
+    world.add_timer_support();
+    let entity = world.spawn().with(Timer::new(Duration::from_secs(1), false)).id();
+
+    world.get_resource_mut::<DeltaTime>().delta = Duration::from_secs(2);
+    world.run_system_once(advance_timers_system);
+
+    assert!(world.get_component_by_entity::<Timer>(entity).finished);
+}